@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::Value;
+
+use crate::tool_catalog::is_known_tool;
+
+/// A user-defined shortcut for a tool call. Templates may reference
+/// positional (`${1}`) and named (`${name}`, `${name:-default}`) arguments,
+/// which turns the alias into a parameterized macro.
+#[derive(Debug, Clone)]
+pub struct Alias {
+    pub name: String,
+    pub tool_name: String,
+    pub template: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct AliasSet {
+    aliases: BTreeMap<String, Alias>,
+}
+
+impl AliasSet {
+    pub fn from_definitions(definitions: &BTreeMap<String, String>) -> Result<Self> {
+        let mut aliases = BTreeMap::new();
+        for (name, definition) in definitions {
+            let alias = Alias::parse(name, definition)?;
+            aliases.insert(name.clone(), alias);
+        }
+        Ok(Self { aliases })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Alias> {
+        self.aliases.get(name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Alias> {
+        self.aliases.values()
+    }
+}
+
+impl Alias {
+    fn parse(name: &str, definition: &str) -> Result<Self> {
+        if name.trim().is_empty() || name.chars().any(char::is_whitespace) {
+            bail!("Invalid alias name `{name}`");
+        }
+        if is_known_tool(name) {
+            bail!("Alias `{name}` conflicts with a built-in tool");
+        }
+
+        let trimmed = definition.trim();
+        let (tool_name, template) = match trimmed.split_once(char::is_whitespace) {
+            Some((tool, rest)) => (tool, Some(rest.trim().to_string())),
+            None => (trimmed, None),
+        };
+        if !is_known_tool(tool_name) {
+            bail!("Alias `{name}` refers to unknown tool `{tool_name}`");
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            tool_name: tool_name.to_string(),
+            template: template.filter(|value| !value.is_empty()),
+        })
+    }
+
+    pub fn definition(&self) -> String {
+        match &self.template {
+            Some(template) => format!("{} {}", self.tool_name, template),
+            None => self.tool_name.clone(),
+        }
+    }
+
+    /// Expands the template into a params object.
+    pub fn expand(&self, positional: &[String], named: &BTreeMap<String, String>) -> Result<Value> {
+        let Some(template) = &self.template else {
+            if !positional.is_empty() || !named.is_empty() {
+                bail!("Alias `{}` does not take arguments", self.name);
+            }
+            return Ok(Value::Object(serde_json::Map::new()));
+        };
+
+        let (expanded, max_positional) = substitute(&self.name, template, positional, named)?;
+        if positional.len() > max_positional {
+            bail!(
+                "Alias `{}` takes {max_positional} positional argument(s) but {} were given",
+                self.name,
+                positional.len()
+            );
+        }
+
+        let value: Value = serde_json::from_str(&expanded).with_context(|| {
+            format!("Alias `{}` expanded to invalid JSON: {expanded}", self.name)
+        })?;
+        if !value.is_object() {
+            bail!("Alias `{}` must expand to a JSON object", self.name);
+        }
+        Ok(value)
+    }
+}
+
+fn substitute(
+    alias_name: &str,
+    template: &str,
+    positional: &[String],
+    named: &BTreeMap<String, String>,
+) -> Result<(String, usize)> {
+    let chars = template.chars().collect::<Vec<_>>();
+    let mut out = String::with_capacity(template.len());
+    let mut in_string = false;
+    let mut escape = false;
+    let mut max_positional = 0usize;
+    let mut idx = 0usize;
+
+    while idx < chars.len() {
+        let ch = chars[idx];
+        if ch == '$' && chars.get(idx + 1) == Some(&'{') {
+            let close = chars[idx + 2..]
+                .iter()
+                .position(|c| *c == '}')
+                .map(|offset| idx + 2 + offset)
+                .ok_or_else(|| anyhow!("Unclosed placeholder in alias `{alias_name}`"))?;
+            let body = chars[idx + 2..close].iter().collect::<String>();
+            let (key, default) = match body.split_once(":-") {
+                Some((key, default)) => (key.trim(), Some(default)),
+                None => (body.trim(), None),
+            };
+
+            let value = if let Ok(position) = key.parse::<usize>() {
+                if position == 0 {
+                    bail!("Alias `{alias_name}` uses ${{0}}; positions start at 1");
+                }
+                max_positional = max_positional.max(position);
+                positional.get(position - 1).map(String::as_str)
+            } else {
+                named.get(key).map(String::as_str)
+            }
+            .or(default)
+            .ok_or_else(|| anyhow!("Alias `{alias_name}` requires argument `${{{key}}}`"))?;
+
+            if in_string {
+                let encoded = serde_json::to_string(value)?;
+                out.push_str(&encoded[1..encoded.len() - 1]);
+            } else {
+                out.push_str(value);
+            }
+            idx = close + 1;
+            continue;
+        }
+
+        if ch == '"' && !escape {
+            in_string = !in_string;
+        }
+        escape = ch == '\\' && !escape;
+        out.push(ch);
+        idx += 1;
+    }
+
+    Ok((out, max_positional))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AliasSet;
+    use std::collections::BTreeMap;
+
+    fn aliases(entries: &[(&str, &str)]) -> AliasSet {
+        let definitions = entries
+            .iter()
+            .map(|(name, definition)| (name.to_string(), definition.to_string()))
+            .collect::<BTreeMap<_, _>>();
+        AliasSet::from_definitions(&definitions).expect("aliases should parse")
+    }
+
+    #[test]
+    fn alias_expands_fixed_params() {
+        let set = aliases(&[(
+            "errors",
+            r#"read_console {"logTypes":["Error","Exception"],"includeStackTrace":true}"#,
+        )]);
+        let alias = set.get("errors").expect("alias should exist");
+        let params = alias
+            .expand(&[], &BTreeMap::new())
+            .expect("expansion should succeed");
+
+        assert_eq!(alias.tool_name, "read_console");
+        assert_eq!(params["logTypes"][1], "Exception");
+        assert_eq!(params["includeStackTrace"], true);
+    }
+
+    #[test]
+    fn macro_substitutes_positional_named_and_default_values() {
+        let set = aliases(&[(
+            "findgo",
+            r#"find_gameobject {"name":"${1}","exactMatch":${exact:-false},"tag":"${tag:-Untagged}"}"#,
+        )]);
+        let alias = set.get("findgo").expect("alias should exist");
+        let mut named = BTreeMap::new();
+        named.insert("exact".to_string(), "true".to_string());
+
+        let params = alias
+            .expand(&["Player \"One\"".to_string()], &named)
+            .expect("expansion should succeed");
+
+        assert_eq!(params["name"], "Player \"One\"");
+        assert_eq!(params["exactMatch"], true);
+        assert_eq!(params["tag"], "Untagged");
+    }
+
+    #[test]
+    fn macro_reports_missing_and_extra_arguments() {
+        let set = aliases(&[("findgo", r#"find_gameobject {"name":"${1}"}"#)]);
+        let alias = set.get("findgo").expect("alias should exist");
+
+        let missing = alias
+            .expand(&[], &BTreeMap::new())
+            .expect_err("missing argument should fail");
+        assert!(format!("{missing:#}").contains("${1}"));
+
+        let extra = alias
+            .expand(&["a".to_string(), "b".to_string()], &BTreeMap::new())
+            .expect_err("extra argument should fail");
+        assert!(format!("{extra:#}").contains("takes 1 positional"));
+    }
+
+    #[test]
+    fn alias_definitions_are_validated() {
+        let mut definitions = BTreeMap::new();
+        definitions.insert("ping".to_string(), "read_console".to_string());
+        assert!(AliasSet::from_definitions(&definitions).is_err());
+
+        let mut definitions = BTreeMap::new();
+        definitions.insert("oops".to_string(), "no_such_tool {}".to_string());
+        assert!(AliasSet::from_definitions(&definitions).is_err());
+    }
+}
@@ -1,12 +1,56 @@
 use std::path::PathBuf;
 
-use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use clap::{ArgAction, ArgGroup, Args, Parser, Subcommand, ValueEnum};
+use serde_json::Value;
 
 #[derive(Debug, Clone, Copy, ValueEnum, Default)]
 pub enum OutputFormat {
     #[default]
     Text,
     Json,
+    Yaml,
+    Ndjson,
+    Table,
+}
+
+/// What to do when the bridge rejects a command with `PLAY_MODE_BLOCKED`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PlayModePolicy {
+    /// Report the error.
+    Fail,
+    /// Call `stop_game`, wait for Edit Mode and retry.
+    Stop,
+    /// Wait until Play Mode ends on its own, then retry.
+    Wait,
+}
+
+/// Editor view captured by `screenshot`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ScreenshotView {
+    Game,
+    Scene,
+}
+
+/// CI report written by `test report`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// JUnit XML.
+    Junit,
+    /// TAP version 13.
+    Tap,
+    /// GitHub Actions `::error` annotations.
+    Github,
+}
+
+/// Trace file written by `profiler record`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TraceFormat {
+    /// Trace Event JSON for chrome://tracing and Perfetto.
+    Chrome,
+    /// speedscope file format.
+    Speedscope,
+    /// One row per sample, one column per metric.
+    Csv,
 }
 
 #[derive(Debug, Parser)]
@@ -20,6 +64,28 @@ pub struct Cli {
     #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
     pub output: OutputFormat,
 
+    /// jq/JSONPath-style selector applied before printing (e.g. `.results[].name`).
+    #[arg(long, global = true, value_name = "EXPR")]
+    pub query: Option<String>,
+
+    /// Go-template style line format (e.g. `{{.id}} {{.status}}`); arrays print one line per item.
+    #[arg(long, global = true, value_name = "TEMPLATE")]
+    pub format: Option<String>,
+
+    /// Unity project directory (or any directory inside it); overrides
+    /// `UNITY_PROJECT_ROOT` and detection from the current directory.
+    #[arg(long, global = true, value_name = "DIR")]
+    pub project: Option<PathBuf>,
+
+    /// Config profile from `unity-cli.toml` or the user config file.
+    #[arg(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Registered instance name or `host:port` id to talk to; overrides
+    /// `--host`/`--port` and configured settings.
+    #[arg(long, global = true, value_name = "NAME|ID", conflicts_with_all = ["host", "port"])]
+    pub instance: Option<String>,
+
     #[arg(long, global = true)]
     pub host: Option<String>,
 
@@ -29,6 +95,11 @@ pub struct Cli {
     #[arg(long, global = true, value_name = "MS")]
     pub timeout_ms: Option<u64>,
 
+    /// Handling of commands the bridge blocks during Play Mode; overrides the
+    /// `play_mode_policy` setting (default `fail`).
+    #[arg(long, global = true, value_enum, value_name = "POLICY")]
+    pub play_mode_policy: Option<PlayModePolicy>,
+
     #[arg(short, long, global = true, action = ArgAction::Count)]
     pub verbose: u8,
 
@@ -36,6 +107,14 @@ pub struct Cli {
     pub command: Command,
 }
 
+impl Cli {
+    /// Whether commands with a human-oriented text view should print the
+    /// underlying value instead.
+    pub fn wants_structured_output(&self) -> bool {
+        !matches!(self.output, OutputFormat::Text) || self.query.is_some() || self.format.is_some()
+    }
+}
+
 #[derive(Debug, Subcommand)]
 pub enum Command {
     Raw(RawArgs),
@@ -55,6 +134,34 @@ pub enum Command {
         #[command(subcommand)]
         command: InstancesCommand,
     },
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    Test {
+        #[command(subcommand)]
+        command: TestCommand,
+    },
+    Console {
+        #[command(subcommand)]
+        command: ConsoleCommand,
+    },
+    /// Run a command and fail if it makes the editor log errors.
+    Guard(GuardArgs),
+    Play {
+        #[command(subcommand)]
+        command: PlayCommand,
+    },
+    /// Capture the Game or Scene view to a local PNG.
+    Screenshot(ScreenshotArgs),
+    Visual {
+        #[command(subcommand)]
+        command: VisualCommand,
+    },
+    Profiler {
+        #[command(subcommand)]
+        command: ProfilerCommand,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -72,8 +179,17 @@ pub struct RawArgs {
     #[arg(long, value_name = "JSON")]
     pub json: Option<String>,
 
+    /// Params file (`.json`, `.json5`, `.yaml`/`.yml`, `.toml`), or `-` for stdin.
     #[arg(long, value_name = "FILE")]
     pub params_file: Option<PathBuf>,
+
+    /// Sets a single param (`a.b=value`, `key:=<json>`, `key=@file`); repeatable.
+    #[arg(short = 'p', long = "param", value_name = "KEY=VALUE")]
+    pub params: Vec<String>,
+
+    /// Params expanded from an alias; `--json`/`--params-file` merge on top.
+    #[arg(skip)]
+    pub base_params: Option<Value>,
 }
 
 #[derive(Debug, Subcommand)]
@@ -106,16 +222,338 @@ pub enum InstancesCommand {
         #[arg(long, value_name = "CSV")]
         ports: Option<String>,
 
+        /// Port range to scan (e.g. `6400-6420`); editors answering the
+        /// bridge protocol are added to the registry.
+        #[arg(long, value_name = "START-END")]
+        scan: Option<String>,
+
         #[arg(long, default_value = "localhost")]
         host: String,
 
         #[arg(long, value_name = "MS", default_value_t = 1000)]
         timeout_ms: u64,
     },
+    /// Make an instance (name or `host:port` id) the default target.
     SetActive {
         id: String,
 
         #[arg(long, value_name = "MS", default_value_t = 1000)]
         timeout_ms: u64,
     },
+    /// Register an instance by `host:port` id, optionally with a name.
+    Add {
+        id: String,
+
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Remove an instance by name or id.
+    Remove { instance: String },
+    /// Give a registered instance a new name.
+    Rename { instance: String, name: String },
+    /// Remove instances that fail their health check.
+    #[command(group(ArgGroup::new("selector").required(true).multiple(true).args(["down", "stale"])))]
+    Prune {
+        /// Remove every instance reported as `down`.
+        #[arg(long)]
+        down: bool,
+
+        /// Remove `down` instances whose editor process has exited.
+        #[arg(long)]
+        stale: bool,
+
+        #[arg(long, value_name = "MS", default_value_t = 1000)]
+        timeout_ms: u64,
+    },
+    /// Print the set-active log, oldest first.
+    History {
+        /// Only print the most recent N activations.
+        #[arg(long, value_name = "N")]
+        limit: Option<usize>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Print every effective setting and where it came from.
+    Show,
+    /// Print one effective setting (e.g. `port`, `lsp.mode`, `aliases.errors`).
+    Get { key: String },
+    /// Write a setting to the project `unity-cli.toml` (or the user file with
+    /// `--user`), under the `--profile` section when given.
+    Set {
+        key: String,
+        value: String,
+
+        #[arg(long)]
+        user: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TestCommand {
+    /// Run Unity tests, wait for them to finish and print a summary; exits
+    /// non-zero when a test fails or the project does not compile.
+    Run(TestRunArgs),
+    /// Convert the last completed test run (or an exported results file) to a
+    /// CI report; pick `junit` (default), `tap` or `github` with `--report-format`.
+    Report(TestReportArgs),
+    /// List the tests that failed or flaked most often in recorded runs.
+    Flaky {
+        /// Only print the N most unstable tests.
+        #[arg(long, value_name = "N", default_value_t = 20)]
+        limit: usize,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct TestRunArgs {
+    /// `EditMode`, `PlayMode` or `All` (case-insensitive).
+    #[arg(long, default_value = "EditMode")]
+    pub mode: String,
+
+    /// Full test name to run.
+    #[arg(long)]
+    pub filter: Option<String>,
+
+    #[arg(long)]
+    pub category: Option<String>,
+
+    /// Assembly name to run.
+    #[arg(long)]
+    pub namespace: Option<String>,
+
+    /// Give up waiting after this many seconds (the run continues in the editor).
+    #[arg(long, value_name = "SECS", default_value_t = 1800)]
+    pub max_wait_sec: u64,
+
+    /// Re-run each failing test on its own up to N times; tests that pass on
+    /// a rerun are reported as flaky instead of failed.
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    pub rerun_failed: u32,
+}
+
+#[derive(Debug, Args)]
+pub struct TestReportArgs {
+    /// NUnit XML or exported JSON results to convert instead of asking the editor.
+    #[arg(long, value_name = "FILE")]
+    pub input: Option<PathBuf>,
+
+    /// Report format.
+    #[arg(long, value_enum, default_value_t = ReportFormat::Junit)]
+    pub report_format: ReportFormat,
+
+    /// Write the report to this file instead of stdout.
+    #[arg(long, value_name = "FILE")]
+    pub out: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConsoleCommand {
+    /// Print recent Unity console entries and optionally follow new ones.
+    Tail(ConsoleTailArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ConsoleTailArgs {
+    /// Keep polling for new entries until interrupted.
+    #[arg(short, long)]
+    pub follow: bool,
+
+    /// Comma-separated log types to show: Log, Warning, Error, Assert, Exception.
+    #[arg(long, value_delimiter = ',', value_name = "TYPES")]
+    pub types: Vec<String>,
+
+    /// Only show entries whose message matches this regex.
+    #[arg(long, value_name = "REGEX")]
+    pub grep: Option<String>,
+
+    /// Exit successfully once a new entry matching this regex appears (implies --follow).
+    #[arg(long, value_name = "REGEX")]
+    pub until_pattern: Option<String>,
+
+    /// Fail when --until-pattern has not matched after this many seconds.
+    #[arg(long, value_name = "SECS", requires = "until_pattern")]
+    pub max_wait_sec: Option<u64>,
+
+    /// Print each entry's stack trace below it.
+    #[arg(long)]
+    pub stack_traces: bool,
+
+    /// Number of existing entries to print first.
+    #[arg(short = 'n', long, value_name = "N", default_value_t = 20)]
+    pub lines: u64,
+
+    /// Poll interval while following.
+    #[arg(long, value_name = "MS", default_value_t = 500)]
+    pub interval_ms: u64,
+}
+
+#[derive(Debug, Args)]
+pub struct GuardArgs {
+    /// Comma-separated log types that fail the guard.
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "TYPES",
+        default_value = "Error,Exception"
+    )]
+    pub fail_on: Vec<String>,
+
+    /// Ignore entries whose message matches this regex (repeatable).
+    #[arg(long, value_name = "REGEX")]
+    pub allow: Vec<String>,
+
+    /// File with one allow regex per line (`#` starts a comment).
+    #[arg(long, value_name = "FILE")]
+    pub allowlist: Option<PathBuf>,
+
+    /// Command to run, e.g. `-- unity-cli raw build_index` or `-- ./smoke.sh`.
+    #[arg(last = true, required = true, value_name = "COMMAND")]
+    pub command: Vec<String>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum PlayCommand {
+    /// Enter Play Mode.
+    Start {
+        /// Wait until the editor is actually playing.
+        #[arg(long)]
+        wait: bool,
+    },
+    /// Toggle pause while playing.
+    Pause,
+    /// Leave Play Mode.
+    Stop {
+        /// Wait until the editor has left Play Mode.
+        #[arg(long)]
+        wait: bool,
+    },
+    /// Print whether the editor is playing, paused or stopped.
+    Status,
+    /// Play for a fixed time, collecting screenshots, logs and state changes
+    /// into a bundle directory; Play Mode is always stopped afterwards.
+    Session(PlaySessionArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct PlaySessionArgs {
+    /// How long to stay in Play Mode, e.g. `30s`, `2m`, `500ms`.
+    #[arg(long, value_name = "DURATION")]
+    pub duration: String,
+
+    /// Save a Game view screenshot at this interval.
+    #[arg(long, value_name = "DURATION")]
+    pub screenshot_every: Option<String>,
+
+    /// Save the console entries logged during the session.
+    #[arg(long)]
+    pub collect_logs: bool,
+
+    /// Bundle directory (default: `play-session-<timestamp>`).
+    #[arg(long, value_name = "DIR")]
+    pub out: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct ScreenshotArgs {
+    #[arg(long, value_enum, default_value = "game")]
+    pub view: ScreenshotView,
+
+    #[arg(long)]
+    pub width: Option<u32>,
+
+    #[arg(long)]
+    pub height: Option<u32>,
+
+    /// Output file (default: `screenshot-<view>-<timestamp>.png`).
+    #[arg(long, value_name = "FILE", conflicts_with = "open_dir")]
+    pub out: Option<PathBuf>,
+
+    /// Append to a numbered `shot-NNNN.png` sequence in this directory.
+    #[arg(long, value_name = "DIR")]
+    pub open_dir: Option<PathBuf>,
+
+    /// Number of shots to add to the sequence.
+    #[arg(long, default_value_t = 1, requires = "open_dir")]
+    pub count: u32,
+
+    /// Delay between shots in a sequence, e.g. `500ms`, `2s`.
+    #[arg(
+        long,
+        value_name = "DURATION",
+        default_value = "1s",
+        requires = "open_dir"
+    )]
+    pub every: String,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum VisualCommand {
+    /// Compare PNGs in two directories and fail above a difference threshold.
+    Compare(VisualCompareArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct VisualCompareArgs {
+    /// Directory with the reference images.
+    #[arg(long, value_name = "DIR")]
+    pub baseline: PathBuf,
+
+    /// Directory with the new images, matched by relative path.
+    #[arg(long, value_name = "DIR")]
+    pub current: PathBuf,
+
+    /// Largest tolerated share of differing pixels per image, e.g. `0.5%`.
+    #[arg(long, value_name = "PERCENT", default_value = "0%")]
+    pub threshold: String,
+
+    /// Perceptual sensitivity per pixel, 0 (exact) to 1 (anything goes).
+    #[arg(long, value_name = "0..1", default_value_t = 0.1)]
+    pub pixel_threshold: f64,
+
+    /// JSON file with regions to ignore.
+    #[arg(long, value_name = "FILE")]
+    pub mask: Option<PathBuf>,
+
+    /// Report directory for diff images, `report.json` and `report.html`.
+    #[arg(long, value_name = "DIR", default_value = "visual-report")]
+    pub out: PathBuf,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ProfilerCommand {
+    /// Record profiler metrics and write them as a trace.
+    Record(ProfilerRecordArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ProfilerRecordArgs {
+    /// How long to record, e.g. `10s`, `1m`.
+    #[arg(long, value_name = "DURATION")]
+    pub duration: String,
+
+    /// Output file (default: `profile.json`, or `profile.csv` for csv).
+    #[arg(long, value_name = "FILE")]
+    pub out: Option<PathBuf>,
+
+    /// Trace format (default: from the `--out` extension, else `chrome`).
+    #[arg(long, value_enum)]
+    pub trace_format: Option<TraceFormat>,
+
+    /// Comma-separated metric names (default: every listed metric).
+    #[arg(long, value_delimiter = ',', value_name = "NAMES")]
+    pub metrics: Vec<String>,
+
+    /// Sampling interval in milliseconds.
+    #[arg(long, value_name = "MS", default_value_t = 250)]
+    pub interval_ms: u64,
+
+    /// Use deep profiling.
+    #[arg(long)]
+    pub deep: bool,
+
+    /// Also have the editor save a Profiler `.data` file.
+    #[arg(long)]
+    pub save_data: bool,
 }
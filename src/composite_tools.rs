@@ -0,0 +1,455 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{json, Map, Value};
+use tokio::time::sleep;
+
+use crate::config::RuntimeConfig;
+use crate::transport::UnityClient;
+use crate::{instances, local_tools, lsp};
+
+const STATUS_POLL_INTERVAL_MS: u64 = 250;
+const FINALIZE_TIMEOUT_MS: u64 = 15_000;
+const SERVER_INFO_TIMEOUT_MS: u64 = 3_000;
+
+pub async fn maybe_execute_composite_tool(
+    config: &RuntimeConfig,
+    tool_name: &str,
+    params: &Value,
+) -> Option<Result<Value>> {
+    match tool_name {
+        "video_capture_for" => Some(video_capture_for(config, params).await),
+        "get_server_info" => Some(get_server_info(config).await),
+        _ => None,
+    }
+}
+
+async fn video_capture_for(config: &RuntimeConfig, params: &Value) -> Result<Value> {
+    let duration_sec = params
+        .get("durationSec")
+        .and_then(Value::as_f64)
+        .ok_or_else(|| anyhow!("video_capture_for requires `durationSec`"))?;
+    if !duration_sec.is_finite() || duration_sec <= 0.0 {
+        bail!("`durationSec` must be a positive number");
+    }
+    let play = params.get("play").and_then(Value::as_bool).unwrap_or(false);
+
+    let mut start_params = params.as_object().cloned().unwrap_or_default();
+    start_params.remove("durationSec");
+    start_params.remove("play");
+
+    let mut client = connect(config).await?;
+
+    // One Ctrl-C listener covers starting and waiting, so an interrupt at any
+    // point skips straight to the stop step; while it is installed Ctrl-C no
+    // longer terminates the process, so the stop below always runs.
+    let recording = tokio::select! {
+        started = start_and_wait(&mut client, play, start_params, duration_sec) => Some(started),
+        _ = tokio::signal::ctrl_c() => None,
+    };
+    let interrupted = recording.is_none();
+    let started = match recording {
+        Some(Ok(started)) => started,
+        Some(Err(error)) => {
+            if play {
+                let _ = client.call_tool("stop_game", json!({})).await;
+            }
+            return Err(error);
+        }
+        // The interrupted call may still be in flight on this connection.
+        None => {
+            client = connect(config).await?;
+            Value::Null
+        }
+    };
+
+    // The stop step must run no matter how the wait ended, so retry once on a
+    // fresh connection if the original one went away during the recording.
+    let stopped = match call_checked(&mut client, "capture_video_stop", json!({})).await {
+        Ok(value) => Ok(value),
+        Err(_) => match connect(config).await {
+            Ok(fresh) => {
+                client = fresh;
+                call_checked(&mut client, "capture_video_stop", json!({})).await
+            }
+            Err(error) => Err(error),
+        },
+    };
+
+    let finalized = match &stopped {
+        Ok(_) => wait_until_finalized(&mut client).await,
+        Err(_) => Ok(false),
+    };
+
+    if play {
+        let _ = client.call_tool("stop_game", json!({})).await;
+    }
+
+    let stopped = stopped.context("Failed to stop video capture")?;
+    let finalized = finalized?;
+
+    let field = |key: &str| {
+        stopped
+            .get(key)
+            .or_else(|| started.get(key))
+            .cloned()
+            .unwrap_or(Value::Null)
+    };
+
+    Ok(json!({
+        "success": true,
+        "recordingId": field("recordingId"),
+        "outputPath": field("outputPath"),
+        "durationSec": stopped.get("durationSec").cloned().unwrap_or(json!(duration_sec)),
+        "frames": stopped.get("frames").cloned().unwrap_or(Value::Null),
+        "fps": field("fps"),
+        "requestedDurationSec": duration_sec,
+        "played": play,
+        "finalized": finalized,
+        "interrupted": interrupted
+    }))
+}
+
+async fn get_server_info(config: &RuntimeConfig) -> Result<Value> {
+    let project = match local_tools::project_root() {
+        Ok(root) => {
+            let index = local_tools::index_status(&root);
+            json!({
+                "root": root.to_string_lossy(),
+                "hasAssets": root.join("Assets").is_dir(),
+                "hasProjectSettings": root.join("ProjectSettings/ProjectVersion.txt").is_file(),
+                "index": index
+            })
+        }
+        Err(error) => json!({ "error": format!("{error:#}") }),
+    };
+
+    let instances = match instances::registry_summary() {
+        Ok(summary) => serde_json::to_value(summary)?,
+        Err(error) => json!({ "error": format!("{error:#}") }),
+    };
+
+    Ok(json!({
+        "success": true,
+        "cli": {
+            "name": env!("CARGO_PKG_NAME"),
+            "version": env!("CARGO_PKG_VERSION")
+        },
+        "config": {
+            "host": config.host,
+            "port": config.port,
+            "timeoutMs": config.timeout.as_millis() as u64,
+            "profile": crate::config::layers().profile()
+        },
+        "instances": instances,
+        "project": project,
+        "lsp": lsp::describe(),
+        "bridge": bridge_info(config).await
+    }))
+}
+
+/// Collects bridge facts from `ping` and `get_editor_info`; an unreachable
+/// bridge is reported rather than treated as an error.
+async fn bridge_info(config: &RuntimeConfig) -> Value {
+    let probe_config = RuntimeConfig {
+        timeout: config
+            .timeout
+            .min(Duration::from_millis(SERVER_INFO_TIMEOUT_MS)),
+        ..config.clone()
+    };
+
+    let mut client = match connect(&probe_config).await {
+        Ok(client) => client,
+        Err(error) => {
+            return json!({ "reachable": false, "error": format!("{error:#}") });
+        }
+    };
+
+    let mut info = json!({ "reachable": true });
+    match client.call_tool_raw("ping", json!({})).await {
+        Ok(response) => {
+            info["packageVersion"] = response
+                .pointer("/editorState/version")
+                .cloned()
+                .unwrap_or(Value::Null);
+            info["isPlaying"] = response
+                .pointer("/editorState/isPlaying")
+                .cloned()
+                .unwrap_or(Value::Null);
+        }
+        Err(error) => {
+            info["reachable"] = Value::Bool(false);
+            info["error"] = Value::String(format!("{error:#}"));
+            return info;
+        }
+    }
+
+    match client.call_tool("get_editor_info", json!({})).await {
+        Ok(editor) => {
+            info["unityVersion"] = editor
+                .pointer("/unity/unityVersion")
+                .cloned()
+                .unwrap_or(Value::Null);
+            info["projectName"] = editor
+                .pointer("/unity/productName")
+                .cloned()
+                .unwrap_or(Value::Null);
+            info["platform"] = editor
+                .pointer("/unity/platform")
+                .cloned()
+                .unwrap_or(Value::Null);
+            info["projectRoot"] = editor.get("projectRoot").cloned().unwrap_or(Value::Null);
+        }
+        Err(error) => {
+            info["editorInfoError"] = Value::String(format!("{error:#}"));
+        }
+    }
+
+    info
+}
+
+async fn connect(config: &RuntimeConfig) -> Result<UnityClient> {
+    UnityClient::connect(config).await.with_context(|| {
+        format!(
+            "Failed to connect to Unity at {}:{}",
+            config.host, config.port
+        )
+    })
+}
+
+/// Calls a bridge tool and treats an `error` field inside the result as a failure.
+async fn call_checked(client: &mut UnityClient, tool_name: &str, params: Value) -> Result<Value> {
+    let value = client.call_tool(tool_name, params).await?;
+    if let Some(error) = value.get("error").and_then(Value::as_str) {
+        let code = value
+            .get("code")
+            .and_then(Value::as_str)
+            .unwrap_or("UNKNOWN_ERROR");
+        bail!("{tool_name} failed: {error} (code: {code})");
+    }
+    Ok(value)
+}
+
+/// Enters Play Mode when asked, starts the recording and waits out its
+/// duration; returns the start result.
+async fn start_and_wait(
+    client: &mut UnityClient,
+    play: bool,
+    start_params: Map<String, Value>,
+    duration_sec: f64,
+) -> Result<Value> {
+    if play {
+        call_checked(client, "play_game", json!({})).await?;
+    }
+    let started = call_checked(client, "capture_video_start", Value::Object(start_params)).await?;
+    sleep(Duration::from_secs_f64(duration_sec)).await;
+    Ok(started)
+}
+
+async fn wait_until_finalized(client: &mut UnityClient) -> Result<bool> {
+    let deadline = Instant::now() + Duration::from_millis(FINALIZE_TIMEOUT_MS);
+    loop {
+        let status = call_checked(client, "capture_video_status", json!({})).await?;
+        let recording = status
+            .get("isRecording")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        if !recording {
+            return Ok(true);
+        }
+        if Instant::now() >= deadline {
+            return Ok(false);
+        }
+        sleep(Duration::from_millis(STATUS_POLL_INTERVAL_MS)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::maybe_execute_composite_tool;
+    use crate::test_support::{config, registry_lock, spawn_editor, temp_registry_path};
+    use serde_json::{json, Value};
+    use std::sync::Mutex;
+
+    /// Records the tool name of each request, for asserting the call order.
+    fn record(calls: &Mutex<Vec<String>>, request: &Value) {
+        calls
+            .lock()
+            .expect("lock should succeed")
+            .push(request["type"].as_str().unwrap_or_default().to_string());
+    }
+
+    #[tokio::test]
+    async fn video_capture_for_runs_start_wait_stop_and_status() {
+        static CALLS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        let (port, server) = spawn_editor(|request| {
+            record(&CALLS, request);
+            match request["type"].as_str() {
+                Some("capture_video_start") => {
+                    assert_eq!(request["params"]["fps"], 24);
+                    assert!(request["params"].get("durationSec").is_none());
+                    json!({ "recordingId": "rec1", "outputPath": "captures/rec1.mp4", "fps": 24 })
+                }
+                Some("capture_video_stop") => json!({
+                    "recordingId": "rec1",
+                    "outputPath": "captures/rec1.mp4",
+                    "durationSec": 0.05,
+                    "frames": 3
+                }),
+                Some("capture_video_status") => json!({ "isRecording": false }),
+                _ => json!({}),
+            }
+        })
+        .await;
+
+        let value = maybe_execute_composite_tool(
+            &config(port),
+            "video_capture_for",
+            &json!({ "durationSec": 0.05, "fps": 24, "play": true }),
+        )
+        .await
+        .expect("tool should be handled")
+        .expect("capture should succeed");
+
+        assert_eq!(value["outputPath"], "captures/rec1.mp4");
+        assert_eq!(value["frames"], 3);
+        assert_eq!(value["finalized"], true);
+        assert_eq!(
+            *CALLS.lock().expect("lock should succeed"),
+            vec![
+                "play_game",
+                "capture_video_start",
+                "capture_video_stop",
+                "capture_video_status",
+                "stop_game"
+            ]
+        );
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn video_capture_for_stops_game_when_start_fails() {
+        static CALLS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        let (port, server) = spawn_editor(|request| {
+            record(&CALLS, request);
+            match request["type"].as_str() {
+                Some("capture_video_start") => {
+                    json!({ "error": "Recorder missing", "code": "E_NO_RECORDER" })
+                }
+                _ => json!({}),
+            }
+        })
+        .await;
+
+        let err = maybe_execute_composite_tool(
+            &config(port),
+            "video_capture_for",
+            &json!({ "durationSec": 1, "play": true }),
+        )
+        .await
+        .expect("tool should be handled")
+        .expect_err("start failure should surface");
+
+        assert!(format!("{err:#}").contains("E_NO_RECORDER"));
+        assert_eq!(
+            CALLS.lock().expect("lock should succeed").last().cloned(),
+            Some("stop_game".to_string())
+        );
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn video_capture_for_leaves_missing_frames_null() {
+        let (port, server) = spawn_editor(|request| match request["type"].as_str() {
+            Some("capture_video_start") => json!({ "recordingId": "rec2" }),
+            Some("capture_video_stop") => json!({ "outputPath": "captures/rec2.mp4" }),
+            Some("capture_video_status") => json!({ "isRecording": false }),
+            _ => json!({}),
+        })
+        .await;
+
+        let value = maybe_execute_composite_tool(
+            &config(port),
+            "video_capture_for",
+            &json!({ "durationSec": 0.01 }),
+        )
+        .await
+        .expect("tool should be handled")
+        .expect("capture should succeed");
+
+        assert_eq!(value["recordingId"], "rec2");
+        assert!(value["frames"].is_null());
+        assert_eq!(value["interrupted"], false);
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn video_capture_for_requires_positive_duration() {
+        let err = maybe_execute_composite_tool(
+            &config(1),
+            "video_capture_for",
+            &json!({ "durationSec": 0 }),
+        )
+        .await
+        .expect("tool should be handled")
+        .expect_err("zero duration should be rejected");
+        assert!(format!("{err:#}").contains("positive"));
+    }
+
+    #[tokio::test]
+    #[allow(clippy::await_holding_lock)]
+    async fn get_server_info_reports_unreachable_bridge() {
+        let _guard = registry_lock().lock().expect("lock should succeed");
+        let registry_path = temp_registry_path("server-info-down");
+        std::env::set_var("UNITY_CLI_REGISTRY_PATH", &registry_path);
+        let listener = std::net::TcpListener::bind(("127.0.0.1", 0)).expect("listener should bind");
+        let port = listener
+            .local_addr()
+            .expect("listener should have local addr")
+            .port();
+        drop(listener);
+
+        let value = maybe_execute_composite_tool(&config(port), "get_server_info", &json!({}))
+            .await
+            .expect("tool should be handled")
+            .expect("server info should succeed without a bridge");
+
+        assert_eq!(value["cli"]["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(value["config"]["port"], port);
+        assert_eq!(value["bridge"]["reachable"], false);
+        assert!(value["lsp"]["mode"].is_string());
+        assert_eq!(
+            value["instances"]["path"],
+            registry_path.to_string_lossy().as_ref()
+        );
+        assert_eq!(value["instances"]["entries"], 0);
+        std::env::remove_var("UNITY_CLI_REGISTRY_PATH");
+    }
+
+    #[tokio::test]
+    #[allow(clippy::await_holding_lock)]
+    async fn get_server_info_merges_bridge_facts() {
+        let _guard = registry_lock().lock().expect("lock should succeed");
+        let registry_path = temp_registry_path("server-info-up");
+        std::env::set_var("UNITY_CLI_REGISTRY_PATH", &registry_path);
+        let (port, server) = spawn_editor(|request| match request["type"].as_str() {
+            Some("get_editor_info") => json!({
+                "projectRoot": "/work/Game",
+                "unity": { "unityVersion": "6000.0.1f1", "productName": "Game", "platform": "OSXEditor" }
+            }),
+            _ => json!({ "message": "pong" }),
+        })
+        .await;
+
+        let value = maybe_execute_composite_tool(&config(port), "get_server_info", &json!({}))
+            .await
+            .expect("tool should be handled")
+            .expect("server info should succeed");
+
+        assert_eq!(value["bridge"]["reachable"], true);
+        assert_eq!(value["bridge"]["unityVersion"], "6000.0.1f1");
+        assert_eq!(value["bridge"]["projectName"], "Game");
+        server.abort();
+        std::env::remove_var("UNITY_CLI_REGISTRY_PATH");
+    }
+}
@@ -1,13 +1,17 @@
+use std::collections::BTreeMap;
 use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use std::time::Duration;
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Serialize;
+use serde_json::Value;
 
 use crate::cli::Cli;
 
-const DEFAULT_HOST: &str = "localhost";
-const DEFAULT_PORT: u16 = 6400;
-const DEFAULT_TIMEOUT_MS: u64 = 30_000;
+const PROJECT_CONFIG_FILE: &str = "unity-cli.toml";
 
 #[derive(Debug, Clone)]
 pub struct RuntimeConfig {
@@ -18,9 +22,41 @@ pub struct RuntimeConfig {
 
 impl RuntimeConfig {
     pub fn from_cli(cli: &Cli) -> Result<Self> {
-        let host = cli.host.clone().unwrap_or_else(default_host);
-        let port = cli.port.unwrap_or_else(default_port);
-        let timeout_ms = cli.timeout_ms.unwrap_or_else(default_timeout_ms);
+        let mut host = cli
+            .host
+            .clone()
+            .or_else(|| setting("host").as_str().map(str::to_string))
+            .ok_or_else(|| anyhow!("No host configured"))?;
+        let mut port = match cli.port {
+            Some(port) => port,
+            None => setting("port")
+                .as_u64()
+                .and_then(|port| u16::try_from(port).ok())
+                .ok_or_else(|| anyhow!("No port configured"))?,
+        };
+
+        if let Some(instance) = &cli.instance {
+            (host, port) = crate::instances::resolve_instance(instance)?;
+        }
+
+        // Without an explicit instance, talk to the editor serving this project.
+        let explicit = cli.instance.is_some()
+            || cli.host.is_some()
+            || cli.port.is_some()
+            || !layers().is_default("host")
+            || !layers().is_default("port");
+        if !explicit {
+            let project_root = crate::local_tools::project_root().ok();
+            if let Some(instance) = crate::instances::preferred_instance(project_root.as_deref()) {
+                (host, port) = instance;
+            }
+        }
+        let timeout_ms = match cli.timeout_ms {
+            Some(timeout_ms) => timeout_ms,
+            None => setting("timeout_ms")
+                .as_u64()
+                .ok_or_else(|| anyhow!("No timeout configured"))?,
+        };
 
         Ok(Self {
             host,
@@ -30,26 +66,555 @@ impl RuntimeConfig {
     }
 }
 
-fn default_host() -> String {
-    read_env_string(&[
-        "UNITY_CLI_HOST",
-        "UNITY_MCP_MCP_HOST",
-        "UNITY_MCP_UNITY_HOST",
-    ])
-    .unwrap_or_else(|| DEFAULT_HOST.to_string())
+#[derive(Debug, Clone, Copy)]
+enum SettingKind {
+    Text,
+    Port,
+    Millis,
+    Bool,
+    Choice(&'static [&'static str]),
+}
+
+/// A configurable key. Environment variables win over config files, and
+/// `--host`/`--port`/`--timeout-ms` win over both.
+struct SettingSpec {
+    key: &'static str,
+    env: &'static [&'static str],
+    default: Option<&'static str>,
+    kind: SettingKind,
+}
+
+const SETTINGS: &[SettingSpec] = &[
+    SettingSpec {
+        key: "host",
+        env: &[
+            "UNITY_CLI_HOST",
+            "UNITY_MCP_MCP_HOST",
+            "UNITY_MCP_UNITY_HOST",
+        ],
+        default: Some("localhost"),
+        kind: SettingKind::Text,
+    },
+    SettingSpec {
+        key: "port",
+        env: &["UNITY_CLI_PORT", "UNITY_MCP_PORT"],
+        default: Some("6400"),
+        kind: SettingKind::Port,
+    },
+    SettingSpec {
+        key: "timeout_ms",
+        env: &[
+            "UNITY_CLI_TIMEOUT_MS",
+            "UNITY_MCP_COMMAND_TIMEOUT",
+            "UNITY_MCP_CONNECT_TIMEOUT",
+        ],
+        default: Some("30000"),
+        kind: SettingKind::Millis,
+    },
+    SettingSpec {
+        key: "play_mode_policy",
+        env: &["UNITY_CLI_PLAY_MODE_POLICY"],
+        default: Some("fail"),
+        kind: SettingKind::Choice(&["fail", "stop", "wait"]),
+    },
+    SettingSpec {
+        key: "lsp.mode",
+        env: &["UNITY_CLI_LSP_MODE"],
+        default: Some("off"),
+        kind: SettingKind::Choice(&["off", "auto", "required"]),
+    },
+    SettingSpec {
+        key: "lsp.command",
+        env: &["UNITY_CLI_LSP_COMMAND"],
+        default: None,
+        kind: SettingKind::Text,
+    },
+    SettingSpec {
+        key: "lsp.request_timeout_ms",
+        env: &["UNITY_CLI_LSP_REQUEST_TIMEOUT_MS"],
+        default: Some("60000"),
+        kind: SettingKind::Millis,
+    },
+    SettingSpec {
+        key: "index.exclude_package_cache",
+        env: &[],
+        default: Some("false"),
+        kind: SettingKind::Bool,
+    },
+    SettingSpec {
+        key: "index.scope",
+        env: &[],
+        default: Some("all"),
+        kind: SettingKind::Choice(&["all", "assets", "packages", "embedded", "library"]),
+    },
+];
+
+impl SettingKind {
+    fn parse_toml(self, value: &toml::Value) -> Option<Value> {
+        match (self, value) {
+            (Self::Bool, toml::Value::Boolean(flag)) => Some(Value::Bool(*flag)),
+            (Self::Port | Self::Millis, toml::Value::Integer(number)) => {
+                self.parse_text(&number.to_string())
+            }
+            (Self::Text | Self::Choice(_), toml::Value::String(text)) => self.parse_text(text),
+            _ => None,
+        }
+    }
+
+    fn parse_text(self, raw: &str) -> Option<Value> {
+        let raw = raw.trim();
+        match self {
+            Self::Text => Some(Value::String(raw.to_string())).filter(|_| !raw.is_empty()),
+            Self::Port => raw
+                .parse::<u16>()
+                .ok()
+                .filter(|port| *port > 0)
+                .map(Value::from),
+            Self::Millis => raw
+                .parse::<u64>()
+                .ok()
+                .filter(|timeout| *timeout > 0)
+                .map(Value::from),
+            Self::Bool => raw.parse::<bool>().ok().map(Value::Bool),
+            Self::Choice(choices) => {
+                let lowered = raw.to_ascii_lowercase();
+                choices
+                    .contains(&lowered.as_str())
+                    .then_some(Value::String(lowered))
+            }
+        }
+    }
+
+    fn expected(self) -> String {
+        match self {
+            Self::Text => "a non-empty string".to_string(),
+            Self::Port => "a port number (1-65535)".to_string(),
+            Self::Millis => "a positive number of milliseconds".to_string(),
+            Self::Bool => "true or false".to_string(),
+            Self::Choice(choices) => format!("one of {}", choices.join(", ")),
+        }
+    }
+}
+
+fn find_spec(key: &str) -> Option<&'static SettingSpec> {
+    SETTINGS.iter().find(|spec| spec.key == key)
+}
+
+/// An effective value and where it came from.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolvedSetting {
+    pub key: String,
+    pub value: Value,
+    pub source: String,
 }
 
-fn default_port() -> u16 {
-    read_env_u16(&["UNITY_CLI_PORT", "UNITY_MCP_PORT"]).unwrap_or(DEFAULT_PORT)
+#[derive(Debug)]
+struct Layer {
+    source: String,
+    table: toml::Table,
 }
 
-fn default_timeout_ms() -> u64 {
-    read_env_u64(&[
-        "UNITY_CLI_TIMEOUT_MS",
-        "UNITY_MCP_COMMAND_TIMEOUT",
-        "UNITY_MCP_CONNECT_TIMEOUT",
-    ])
-    .unwrap_or(DEFAULT_TIMEOUT_MS)
+/// Config files in precedence order: the user file, its selected profile,
+/// the project `unity-cli.toml`, then the project's selected profile.
+#[derive(Debug, Default)]
+pub struct ConfigLayers {
+    profile: Option<String>,
+    user_path: Option<PathBuf>,
+    project_path: Option<PathBuf>,
+    layers: Vec<Layer>,
+}
+
+static LAYERS: OnceLock<ConfigLayers> = OnceLock::new();
+
+/// Loads config files once per process, searching for `unity-cli.toml` from
+/// `start_dir` upward. `profile` falls back to `UNITY_CLI_PROFILE`, then to
+/// `default_profile` in the files.
+pub fn init(profile: Option<&str>, start_dir: &Path) -> Result<()> {
+    let profile = profile
+        .map(str::to_string)
+        .or_else(|| read_env_string(&["UNITY_CLI_PROFILE"]));
+    let layers = ConfigLayers::load(config_file_path(), start_dir, profile.as_deref())?;
+    LAYERS
+        .set(layers)
+        .map_err(|_| anyhow!("Configuration was already loaded"))
+}
+
+/// Loaded config files; empty when `init` has not run (e.g. in unit tests).
+pub fn layers() -> &'static ConfigLayers {
+    LAYERS.get_or_init(ConfigLayers::default)
+}
+
+/// Effective value for a built-in key (env, then config files, then the
+/// default); `Value::Null` when unset.
+pub fn setting(key: &str) -> Value {
+    layers()
+        .resolve(key)
+        .map(|resolved| resolved.value)
+        .unwrap_or(Value::Null)
+}
+
+pub fn config_file_path() -> Option<PathBuf> {
+    if let Some(path) = read_env_string(&["UNITY_CLI_CONFIG"]) {
+        return Some(PathBuf::from(path));
+    }
+    dirs::config_dir()
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+        .map(|dir| dir.join("unity-cli").join("config.toml"))
+}
+
+fn find_project_config(start_dir: &Path) -> Option<PathBuf> {
+    start_dir
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_CONFIG_FILE))
+        .find(|candidate| candidate.is_file())
+}
+
+fn read_table(path: &Path) -> Result<Option<toml::Table>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let raw = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+    let table = toml::from_str(&raw)
+        .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+    Ok(Some(table))
+}
+
+fn lookup<'a>(table: &'a toml::Table, key: &str) -> Option<&'a toml::Value> {
+    let mut segments = key.split('.');
+    let mut current = table.get(segments.next()?)?;
+    for segment in segments {
+        current = current.as_table()?.get(segment)?;
+    }
+    Some(current)
+}
+
+impl ConfigLayers {
+    fn load(user_path: Option<PathBuf>, start_dir: &Path, profile: Option<&str>) -> Result<Self> {
+        let mut files = Vec::new();
+        if let Some(path) = &user_path {
+            if let Some(table) = read_table(path)? {
+                files.push((format!("user file {}", path.display()), table));
+            }
+        }
+        let project_path = find_project_config(start_dir);
+        if let Some(path) = &project_path {
+            if let Some(table) = read_table(path)? {
+                files.push((format!("project file {}", path.display()), table));
+            }
+        }
+
+        let profile = profile.map(str::to_string).or_else(|| {
+            files.iter().rev().find_map(|(_, table)| {
+                table
+                    .get("default_profile")
+                    .and_then(toml::Value::as_str)
+                    .map(str::to_string)
+            })
+        });
+
+        let mut layers = Vec::new();
+        let mut profile_found = false;
+        for (source, mut table) in files {
+            let profile_table = table
+                .remove("profiles")
+                .and_then(|profiles| match profiles {
+                    toml::Value::Table(mut profiles) => {
+                        profile.as_ref().and_then(|name| profiles.remove(name))
+                    }
+                    _ => None,
+                });
+            layers.push(Layer {
+                source: source.clone(),
+                table,
+            });
+            if let (Some(name), Some(profile_table)) = (&profile, profile_table) {
+                let toml::Value::Table(table) = profile_table else {
+                    bail!("Profile `{name}` in {source} must be a table");
+                };
+                profile_found = true;
+                layers.push(Layer {
+                    source: format!("{source} (profile {name})"),
+                    table,
+                });
+            }
+        }
+
+        if let (Some(name), false) = (&profile, profile_found) {
+            bail!("Profile `{name}` is not defined in any config file");
+        }
+
+        let loaded = Self {
+            profile,
+            user_path,
+            project_path,
+            layers,
+        };
+        loaded.validate()?;
+        Ok(loaded)
+    }
+
+    fn validate(&self) -> Result<()> {
+        for layer in &self.layers {
+            for spec in SETTINGS {
+                if let Some(value) = lookup(&layer.table, spec.key) {
+                    if spec.kind.parse_toml(value).is_none() {
+                        bail!(
+                            "Invalid `{}` in {}: expected {}",
+                            spec.key,
+                            layer.source,
+                            spec.kind.expected()
+                        );
+                    }
+                }
+            }
+            if let Some(aliases) = layer.table.get("aliases") {
+                let valid = aliases
+                    .as_table()
+                    .is_some_and(|table| table.values().all(toml::Value::is_str));
+                if !valid {
+                    bail!(
+                        "Invalid `aliases` in {}: expected a table of strings",
+                        layer.source
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    fn resolve(&self, key: &str) -> Result<ResolvedSetting> {
+        let spec = find_spec(key).ok_or_else(|| {
+            let keys = SETTINGS.iter().map(|spec| spec.key).collect::<Vec<_>>();
+            anyhow!(
+                "Unknown config key `{key}`. Known keys: {}",
+                keys.join(", ")
+            )
+        })?;
+        let resolved = |value, source: String| ResolvedSetting {
+            key: key.to_string(),
+            value,
+            source,
+        };
+
+        for var in spec.env {
+            if let Some(value) = env::var(var)
+                .ok()
+                .and_then(|raw| spec.kind.parse_text(&raw))
+            {
+                return Ok(resolved(value, format!("env {var}")));
+            }
+        }
+        for layer in self.layers.iter().rev() {
+            if let Some(value) = lookup(&layer.table, key).and_then(|v| spec.kind.parse_toml(v)) {
+                return Ok(resolved(value, layer.source.clone()));
+            }
+        }
+        Ok(
+            match spec.default.and_then(|raw| spec.kind.parse_text(raw)) {
+                Some(value) => resolved(value, "default".to_string()),
+                None => resolved(Value::Null, "unset".to_string()),
+            },
+        )
+    }
+
+    /// Whether `key` falls back to its built-in default.
+    fn is_default(&self, key: &str) -> bool {
+        self.resolve(key)
+            .is_ok_and(|resolved| resolved.source == "default")
+    }
+
+    /// Aliases merged across layers; later layers override earlier ones.
+    pub fn aliases(&self) -> BTreeMap<String, String> {
+        self.alias_sources()
+            .into_iter()
+            .map(|(name, (definition, _))| (name, definition))
+            .collect()
+    }
+
+    fn alias_sources(&self) -> BTreeMap<String, (String, String)> {
+        let mut aliases = BTreeMap::new();
+        for layer in &self.layers {
+            let Some(table) = layer.table.get("aliases").and_then(toml::Value::as_table) else {
+                continue;
+            };
+            for (name, definition) in table {
+                if let Some(definition) = definition.as_str() {
+                    aliases.insert(name.clone(), (definition.to_string(), layer.source.clone()));
+                }
+            }
+        }
+        aliases
+    }
+}
+
+/// Resolves `key` including CLI flag overrides, for `config get`.
+pub fn resolve_setting(cli: &Cli, key: &str) -> Result<ResolvedSetting> {
+    if let Some(name) = key.strip_prefix("aliases.") {
+        let (definition, source) = layers()
+            .alias_sources()
+            .remove(name)
+            .ok_or_else(|| anyhow!("Alias `{name}` is not defined"))?;
+        return Ok(ResolvedSetting {
+            key: key.to_string(),
+            value: Value::String(definition),
+            source,
+        });
+    }
+    if key == "profile" {
+        return Ok(profile_setting(cli));
+    }
+
+    let flag = match key {
+        "host" => cli.host.clone().map(|host| ("--host", Value::String(host))),
+        "port" => cli.port.map(|port| ("--port", Value::from(port))),
+        "timeout_ms" => cli.timeout_ms.map(|ms| ("--timeout-ms", Value::from(ms))),
+        _ => None,
+    };
+    let mut resolved = layers().resolve(key)?;
+    if let Some((flag, value)) = flag {
+        resolved.value = value;
+        resolved.source = format!("flag {flag}");
+    }
+    Ok(resolved)
+}
+
+fn profile_setting(cli: &Cli) -> ResolvedSetting {
+    let source = if cli.profile.is_some() {
+        "flag --profile"
+    } else if read_env_string(&["UNITY_CLI_PROFILE"]).is_some() {
+        "env UNITY_CLI_PROFILE"
+    } else if layers().profile().is_some() {
+        "default_profile"
+    } else {
+        "unset"
+    };
+    ResolvedSetting {
+        key: "profile".to_string(),
+        value: layers()
+            .profile()
+            .map(|name| Value::String(name.to_string()))
+            .unwrap_or(Value::Null),
+        source: source.to_string(),
+    }
+}
+
+/// Every effective setting plus aliases, for `config show`.
+pub fn effective_settings(cli: &Cli) -> Result<Vec<ResolvedSetting>> {
+    let mut settings = vec![profile_setting(cli)];
+    for spec in SETTINGS {
+        settings.push(resolve_setting(cli, spec.key)?);
+    }
+    for (name, (definition, source)) in layers().alias_sources() {
+        settings.push(ResolvedSetting {
+            key: format!("aliases.{name}"),
+            value: Value::String(definition),
+            source,
+        });
+    }
+    Ok(settings)
+}
+
+#[derive(Debug, Serialize)]
+pub struct SetResult {
+    pub key: String,
+    pub value: Value,
+    pub path: String,
+    pub profile: Option<String>,
+}
+
+fn write_setting(path: &Path, parents: &[&str], leaf: &str, value: &Value) -> Result<()> {
+    let existing = if path.exists() {
+        fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?
+    } else {
+        String::new()
+    };
+    let mut document = existing
+        .parse::<toml_edit::DocumentMut>()
+        .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+
+    let mut table = document.as_table_mut();
+    for (depth, name) in parents.iter().enumerate() {
+        let entry = table.entry(name).or_insert_with(|| {
+            let mut child = toml_edit::Table::new();
+            // Only the table holding the value gets its own `[header]`.
+            child.set_implicit(depth + 1 < parents.len());
+            toml_edit::Item::Table(child)
+        });
+        table = entry
+            .as_table_mut()
+            .ok_or_else(|| anyhow!("`{name}` in {} is not a table", path.display()))?;
+    }
+    table[leaf] = toml_edit::value(match value {
+        Value::Bool(flag) => toml_edit::Value::from(*flag),
+        Value::Number(number) => toml_edit::Value::from(number.as_i64().unwrap_or_default()),
+        other => toml_edit::Value::from(other.as_str().unwrap_or_default()),
+    });
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(path, document.to_string())
+        .with_context(|| format!("Failed to write config file: {}", path.display()))
+}
+
+/// Writes `key = value` to the project file (or the user file with `user`),
+/// under `[profiles.<profile>]` when a profile is given. Comments and
+/// formatting in the file are preserved.
+pub fn set_setting(key: &str, raw: &str, user: bool, profile: Option<&str>) -> Result<SetResult> {
+    let (segments, value) = if let Some(name) = key.strip_prefix("aliases.") {
+        if name.is_empty() {
+            bail!("Alias name is required, e.g. aliases.errors");
+        }
+        (vec!["aliases", name], Value::String(raw.to_string()))
+    } else {
+        let spec = find_spec(key).ok_or_else(|| anyhow!("Unknown config key `{key}`"))?;
+        let value = spec.kind.parse_text(raw).ok_or_else(|| {
+            anyhow!(
+                "Invalid value `{raw}` for `{key}`: expected {}",
+                spec.kind.expected()
+            )
+        })?;
+        (key.split('.').collect(), value)
+    };
+
+    let path = if user {
+        layers()
+            .user_path
+            .clone()
+            .or_else(config_file_path)
+            .ok_or_else(|| anyhow!("Unable to resolve the user config file path"))?
+    } else {
+        match &layers().project_path {
+            Some(path) => path.clone(),
+            None => match crate::local_tools::project_root() {
+                Ok(root) => root.join(PROJECT_CONFIG_FILE),
+                Err(_) => env::current_dir()
+                    .context("Failed to resolve current directory")?
+                    .join(PROJECT_CONFIG_FILE),
+            },
+        }
+    };
+
+    let mut parents = Vec::new();
+    if let Some(profile) = profile {
+        parents.extend(["profiles", profile]);
+    }
+    parents.extend(&segments[..segments.len() - 1]);
+    write_setting(&path, &parents, segments[segments.len() - 1], &value)?;
+
+    Ok(SetResult {
+        key: key.to_string(),
+        value,
+        path: path.display().to_string(),
+        profile: profile.map(str::to_string),
+    })
 }
 
 fn read_env_string(keys: &[&str]) -> Option<String> {
@@ -59,16 +624,87 @@ fn read_env_string(keys: &[&str]) -> Option<String> {
         .filter(|value| !value.is_empty())
 }
 
-fn read_env_u16(keys: &[&str]) -> Option<u16> {
-    keys.iter()
-        .find_map(|key| env::var(key).ok())
-        .and_then(|value| value.trim().parse::<u16>().ok())
-        .filter(|port| *port > 0)
-}
+#[cfg(test)]
+mod tests {
+    use super::{write_setting, ConfigLayers};
+    use serde_json::json;
 
-fn read_env_u64(keys: &[&str]) -> Option<u64> {
-    keys.iter()
-        .find_map(|key| env::var(key).ok())
-        .and_then(|value| value.trim().parse::<u64>().ok())
-        .filter(|timeout| *timeout > 0)
+    fn write(path: &std::path::Path, content: &str) {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("dir should be created");
+        }
+        std::fs::write(path, content).expect("file should be written");
+    }
+
+    #[test]
+    fn layers_apply_profiles_and_project_over_user() {
+        let tmp = tempfile::tempdir().expect("temp dir should be created");
+        let user = tmp.path().join("user/config.toml");
+        write(
+            &user,
+            "index.scope = \"assets\"\n[aliases]\nerrs = \"read_console\"\n[profiles.ci]\nindex.exclude_package_cache = true\n",
+        );
+        write(
+            &tmp.path().join("project/unity-cli.toml"),
+            "default_profile = \"ci\"\n[index]\nscope = \"packages\"\n[profiles.ci.aliases]\nerrs = \"clear_console\"\n",
+        );
+        let nested = tmp.path().join("project/Assets/Scripts");
+        std::fs::create_dir_all(&nested).expect("dir should be created");
+
+        let layers = ConfigLayers::load(Some(user), &nested, None).expect("config should load");
+        assert_eq!(layers.profile(), Some("ci"));
+
+        let scope = layers.resolve("index.scope").expect("key should resolve");
+        assert_eq!(scope.value, json!("packages"));
+        assert!(scope.source.starts_with("project file"));
+
+        let exclude = layers
+            .resolve("index.exclude_package_cache")
+            .expect("key should resolve");
+        assert_eq!(exclude.value, json!(true));
+        assert!(exclude.source.ends_with("(profile ci)"));
+
+        assert_eq!(layers.aliases()["errs"], "clear_console");
+        let timeout = layers
+            .resolve("lsp.request_timeout_ms")
+            .expect("key should resolve");
+        assert_eq!(timeout.source, "default");
+    }
+
+    #[test]
+    fn layers_reject_unknown_profiles_and_invalid_values() {
+        let tmp = tempfile::tempdir().expect("temp dir should be created");
+        let user = tmp.path().join("config.toml");
+        write(&user, "[profiles.dev]\nport = 6401\n");
+        let err = ConfigLayers::load(Some(user.clone()), tmp.path(), Some("prod"))
+            .expect_err("unknown profile should fail");
+        assert!(format!("{err:#}").contains("Profile `prod`"));
+
+        write(&user, "[lsp]\nmode = \"sometimes\"\n");
+        let err =
+            ConfigLayers::load(Some(user), tmp.path(), None).expect_err("invalid mode should fail");
+        assert!(format!("{err:#}").contains("one of off, auto, required"));
+    }
+
+    #[test]
+    fn write_setting_preserves_comments_and_nests_profiles() {
+        let tmp = tempfile::tempdir().expect("temp dir should be created");
+        let path = tmp.path().join("unity-cli.toml");
+        write(&path, "# team defaults\nport = 6400\n");
+
+        write_setting(&path, &[], "port", &json!(6401)).expect("port should be written");
+        write_setting(&path, &["profiles", "ci", "lsp"], "mode", &json!("auto"))
+            .expect("profile key should be written");
+
+        let content = std::fs::read_to_string(&path).expect("file should be readable");
+        assert!(content.starts_with("# team defaults\nport = 6401\n"));
+        assert!(content.contains("[profiles.ci.lsp]\nmode = \"auto\""));
+        assert!(!content.contains("[profiles]\n"));
+
+        let layers = ConfigLayers::load(None, tmp.path(), Some("ci")).expect("config should load");
+        assert_eq!(
+            layers.resolve("lsp.mode").expect("resolves").value,
+            json!("auto")
+        );
+    }
 }
@@ -0,0 +1,385 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use serde_json::{json, Value};
+use tokio::time::sleep;
+
+use crate::config::RuntimeConfig;
+use crate::transport::UnityClient;
+
+/// Entries fetched per poll; more new entries than this between two polls are
+/// reported as skipped.
+const POLL_BATCH: u64 = 500;
+/// How long following tolerates a lost connection (domain reloads, entering
+/// Play Mode) before giving up.
+const RECONNECT_GRACE_SECS: u64 = 120;
+
+pub const LOG_TYPES: [&str; 5] = ["Log", "Warning", "Error", "Assert", "Exception"];
+
+#[derive(Debug, Clone)]
+pub struct TailOptions {
+    pub follow: bool,
+    /// Log types to show; empty shows all.
+    pub types: Vec<String>,
+    pub grep: Option<Regex>,
+    /// Stop once a new entry matches.
+    pub until: Option<Regex>,
+    pub stack_traces: bool,
+    /// Existing entries to print before following.
+    pub lines: u64,
+    pub poll_interval: Duration,
+    pub max_wait: Option<Duration>,
+}
+
+impl TailOptions {
+    fn shows(&self, entry: &Value) -> bool {
+        let log_type = entry.get("logType").and_then(Value::as_str).unwrap_or("");
+        let message = entry.get("message").and_then(Value::as_str).unwrap_or("");
+        (self.types.is_empty() || self.types.iter().any(|wanted| wanted == log_type))
+            && self.grep.as_ref().is_none_or(|grep| grep.is_match(message))
+    }
+
+    /// The entry as printed: the stack trace is always requested so it is
+    /// split off `message`, and dropped here unless `--stack-traces` is set.
+    fn present(&self, entry: &Value) -> Value {
+        let mut entry = entry.clone();
+        if !self.stack_traces {
+            if let Some(fields) = entry.as_object_mut() {
+                fields.remove("stackTrace");
+            }
+        }
+        entry
+    }
+}
+
+/// Tracks the console position between polls so each entry is emitted once.
+///
+/// `read_console` entries carry no id, their timestamp is the read time and
+/// the bridge ignores `sinceTimestamp`, so the cursor is the console's total
+/// entry count (`totalCaptured`).
+#[derive(Debug, Default)]
+struct Cursor {
+    seen_total: Option<u64>,
+}
+
+impl Cursor {
+    fn params(&self, options: &TailOptions) -> Value {
+        // Without `includeStackTrace` the bridge leaves the stack inside
+        // `message`, where `--grep` and `--until-pattern` would match it.
+        json!({
+            "count": if self.seen_total.is_some() { POLL_BATCH } else { options.lines },
+            "logTypes": ["All"],
+            "includeStackTrace": true,
+            "format": "detailed",
+            "sortOrder": "oldest",
+        })
+    }
+
+    /// Returns the entries not seen before (oldest first) and how many new
+    /// entries fell outside the fetched batch.
+    fn advance(&mut self, result: &Value) -> (Vec<Value>, u64) {
+        let logs = result
+            .get("logs")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        let total = result
+            .get("totalCaptured")
+            .and_then(Value::as_u64)
+            .unwrap_or(0);
+        let previous = self.seen_total.replace(total);
+
+        let fresh = match previous {
+            None => return (logs, 0),
+            // The console was cleared; everything in it is new.
+            Some(previous) if total < previous => total,
+            Some(previous) => total - previous,
+        };
+        let available = logs.len() as u64;
+        let take = fresh.min(available);
+        let skipped = fresh - take;
+        (logs[(available - take) as usize..].to_vec(), skipped)
+    }
+}
+
+/// How tailing ended.
+#[derive(Debug)]
+pub enum TailEnd {
+    /// Not following, or interrupted while following.
+    Finished,
+    /// An entry matched `--until-pattern`.
+    Matched,
+}
+
+/// Prints recent console entries through `emit` and, when following, polls
+/// `read_console` for new ones until interrupted or `until` matches.
+pub async fn tail<F>(config: &RuntimeConfig, options: &TailOptions, mut emit: F) -> Result<TailEnd>
+where
+    F: FnMut(&Value) -> Result<()>,
+{
+    let mut cursor = Cursor::default();
+    let mut client = connect(config).await?;
+    let backlog = read_console(&mut client, &cursor.params(options)).await?;
+    for entry in cursor
+        .advance(&backlog)
+        .0
+        .iter()
+        .filter(|entry| options.shows(entry))
+    {
+        emit(&options.present(entry))?;
+    }
+    if !options.follow && options.until.is_none() {
+        return Ok(TailEnd::Finished);
+    }
+
+    let started_at = Instant::now();
+    let mut client = Some(client);
+    let mut disconnected_since: Option<Instant> = None;
+    loop {
+        tokio::select! {
+            _ = sleep(options.poll_interval) => {}
+            _ = tokio::signal::ctrl_c() => {
+                if options.until.is_some() {
+                    bail!("Interrupted before the pattern appeared");
+                }
+                return Ok(TailEnd::Finished);
+            }
+        }
+        if let Some(max_wait) = options.max_wait {
+            if started_at.elapsed() > max_wait {
+                bail!("No matching log entry within {}s", max_wait.as_secs());
+            }
+        }
+
+        let params = cursor.params(options);
+        let polled = match client.as_mut() {
+            Some(active) => read_console(active, &params).await,
+            None => match connect(config).await {
+                Ok(fresh) => read_console(client.insert(fresh), &params).await,
+                Err(error) => Err(error),
+            },
+        };
+        let result = match polled {
+            Ok(result) => result,
+            Err(error) => {
+                client = None;
+                let since = *disconnected_since.get_or_insert_with(Instant::now);
+                if since.elapsed() > Duration::from_secs(RECONNECT_GRACE_SECS) {
+                    return Err(
+                        error.context("Lost the connection to Unity while following the console")
+                    );
+                }
+                continue;
+            }
+        };
+        disconnected_since = None;
+
+        let (entries, skipped) = cursor.advance(&result);
+        if skipped > 0 {
+            eprintln!("Warning: {skipped} console entries arrived too fast and were skipped");
+        }
+        for entry in entries.iter().filter(|entry| options.shows(entry)) {
+            emit(&options.present(entry))?;
+            let message = entry.get("message").and_then(Value::as_str).unwrap_or("");
+            if options
+                .until
+                .as_ref()
+                .is_some_and(|until| until.is_match(message))
+            {
+                return Ok(TailEnd::Matched);
+            }
+        }
+    }
+}
+
+/// Calls `read_console`, turning a `success: false` result into an error.
+pub async fn read_console(client: &mut UnityClient, params: &Value) -> Result<Value> {
+    let result = client.call_tool("read_console", params.clone()).await?;
+    if result.get("success").and_then(Value::as_bool) == Some(false) {
+        bail!(
+            "{}",
+            result
+                .get("error")
+                .and_then(Value::as_str)
+                .unwrap_or("read_console failed")
+        );
+    }
+    Ok(result)
+}
+
+async fn connect(config: &RuntimeConfig) -> Result<UnityClient> {
+    UnityClient::connect(config).await.with_context(|| {
+        format!(
+            "Failed to connect to Unity at {}:{}",
+            config.host, config.port
+        )
+    })
+}
+
+/// `[Type] message`, colored by type, with the stack trace indented below.
+pub fn render_entry(entry: &Value, color: bool) -> String {
+    let log_type = entry
+        .get("logType")
+        .and_then(Value::as_str)
+        .unwrap_or("Log");
+    let message = entry.get("message").and_then(Value::as_str).unwrap_or("");
+    let code = match log_type {
+        "Error" | "Exception" | "Assert" => Some("31"),
+        "Warning" => Some("33"),
+        _ => None,
+    };
+    let mut text = match code.filter(|_| color) {
+        Some(code) => format!("\x1b[{code}m[{log_type}] {message}\x1b[0m"),
+        None => format!("[{log_type}] {message}"),
+    };
+    if let Some(stack) = entry
+        .get("stackTrace")
+        .and_then(Value::as_str)
+        .filter(|stack| !stack.trim().is_empty())
+    {
+        for line in stack.lines().filter(|line| !line.trim().is_empty()) {
+            if color {
+                text.push_str(&format!("\n\x1b[2m    {}\x1b[0m", line.trim_end()));
+            } else {
+                text.push_str(&format!("\n    {}", line.trim_end()));
+            }
+        }
+    }
+    text
+}
+
+/// Normalizes `--types` values to the bridge's log type names.
+pub fn parse_types(raw: &[String]) -> Result<Vec<String>> {
+    raw.iter()
+        .map(|value| {
+            LOG_TYPES
+                .into_iter()
+                .find(|known| known.eq_ignore_ascii_case(value.trim()))
+                .map(str::to_string)
+                .with_context(|| {
+                    format!("Unknown log type `{value}`. Use {}.", LOG_TYPES.join(", "))
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_types, render_entry, tail, Cursor, TailEnd, TailOptions};
+    use crate::test_support::{config, spawn_editor};
+    use regex::Regex;
+    use serde_json::{json, Value};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    fn entry(log_type: &str, message: &str) -> Value {
+        json!({ "logType": log_type, "message": message, "timestamp": "2026-01-01T00:00:00Z" })
+    }
+
+    fn options() -> TailOptions {
+        TailOptions {
+            follow: true,
+            types: Vec::new(),
+            grep: None,
+            until: None,
+            stack_traces: false,
+            lines: 2,
+            poll_interval: Duration::from_millis(10),
+            max_wait: Some(Duration::from_secs(5)),
+        }
+    }
+
+    #[test]
+    fn cursor_emits_only_new_entries() {
+        let mut cursor = Cursor::default();
+        let first = json!({ "totalCaptured": 3, "logs": [entry("Log", "b"), entry("Log", "c")] });
+        assert_eq!(cursor.advance(&first).0.len(), 2);
+        let params = cursor.params(&options());
+        assert_eq!(params["includeStackTrace"], true);
+        assert!(params.get("sinceTimestamp").is_none());
+
+        let same = json!({ "totalCaptured": 3, "logs": [entry("Log", "b"), entry("Log", "c")] });
+        assert!(cursor.advance(&same).0.is_empty());
+
+        let grown = json!({
+            "totalCaptured": 5,
+            "logs": [entry("Log", "b"), entry("Log", "c"), entry("Log", "d"), entry("Log", "e")]
+        });
+        let (fresh, skipped) = cursor.advance(&grown);
+        assert_eq!(fresh, vec![entry("Log", "d"), entry("Log", "e")]);
+        assert_eq!(skipped, 0);
+
+        // Cleared console: the whole (shorter) console is new.
+        let cleared = json!({ "totalCaptured": 1, "logs": [entry("Error", "x")] });
+        assert_eq!(cursor.advance(&cleared).0, vec![entry("Error", "x")]);
+
+        let burst = json!({ "totalCaptured": 6, "logs": [entry("Log", "y")] });
+        assert_eq!(cursor.advance(&burst).1, 4);
+    }
+
+    #[tokio::test]
+    async fn tail_follows_until_pattern_matches() {
+        static POLLS: AtomicUsize = AtomicUsize::new(0);
+        let (port, server) = spawn_editor(|request| {
+            assert_eq!(request["type"], "read_console");
+            assert_eq!(request["params"]["includeStackTrace"], true);
+            match POLLS.fetch_add(1, Ordering::SeqCst) {
+                0 => json!({ "success": true, "totalCaptured": 1, "logs": [entry("Log", "old")] }),
+                1 => json!({ "success": true, "totalCaptured": 1, "logs": [entry("Log", "old")] }),
+                _ => json!({
+                    "success": true,
+                    "totalCaptured": 4,
+                    "logs": [
+                        entry("Log", "old"),
+                        entry("Warning", "low ammo"),
+                        json!({
+                            "logType": "Log",
+                            "message": "Level 2 loaded",
+                            "stackTrace": "Loader.Done () (at Assets/Loader.cs:9)"
+                        }),
+                        entry("Error", "after the match")
+                    ]
+                }),
+            }
+        })
+        .await;
+
+        let config = config(port);
+        let tail_options = TailOptions {
+            types: vec!["Log".to_string(), "Error".to_string()],
+            until: Some(Regex::new(r"Level \d+ loaded").expect("regex should compile")),
+            ..options()
+        };
+        let mut seen = Vec::new();
+        let end = tail(&config, &tail_options, |entry| {
+            assert!(entry.get("stackTrace").is_none());
+            seen.push(entry["message"].as_str().unwrap_or("").to_string());
+            Ok(())
+        })
+        .await
+        .expect("tail should finish");
+
+        assert!(matches!(end, TailEnd::Matched));
+        assert_eq!(seen, vec!["old", "Level 2 loaded"]);
+
+        server.abort();
+    }
+
+    #[test]
+    fn entries_render_with_type_colors_and_stack_traces() {
+        let error = json!({ "logType": "Error", "message": "boom", "stackTrace": "A.B () (at Assets/A.cs:3)\n" });
+        assert_eq!(
+            render_entry(&error, false),
+            "[Error] boom\n    A.B () (at Assets/A.cs:3)"
+        );
+        assert!(render_entry(&error, true).starts_with("\x1b[31m[Error] boom\x1b[0m"));
+        assert_eq!(render_entry(&entry("Log", "hi"), true), "[Log] hi");
+
+        assert_eq!(
+            parse_types(&["error".to_string(), "Warning".to_string()]).expect("types should parse"),
+            vec!["Error", "Warning"]
+        );
+        assert!(parse_types(&["Fatal".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,310 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use serde_json::{json, Value};
+use tokio::time::sleep;
+
+use crate::config::RuntimeConfig;
+use crate::console_tail::read_console;
+use crate::transport::UnityClient;
+
+/// How often the console is polled while the wrapped command runs.
+const WATCH_INTERVAL_MS: u64 = 250;
+/// Reads attempted per poll while the console keeps changing.
+const READ_ATTEMPTS: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct GuardOptions {
+    /// Log types that fail the guard.
+    pub fail_on: Vec<String>,
+    /// Messages matching any of these are ignored.
+    pub allow: Vec<Regex>,
+}
+
+impl GuardOptions {
+    /// Splits new console entries into offending and allowlisted ones.
+    pub fn classify(&self, entries: &[Value]) -> (Vec<Value>, usize) {
+        let mut offending = Vec::new();
+        let mut ignored = 0;
+        for entry in entries {
+            let log_type = entry.get("logType").and_then(Value::as_str).unwrap_or("");
+            if !self.fail_on.iter().any(|wanted| wanted == log_type) {
+                continue;
+            }
+            let message = entry.get("message").and_then(Value::as_str).unwrap_or("");
+            if self.allow.iter().any(|allow| allow.is_match(message)) {
+                ignored += 1;
+            } else {
+                offending.push(entry.clone());
+            }
+        }
+        (offending, ignored)
+    }
+}
+
+/// Collects console entries logged after `start`, polled incrementally so a
+/// console clear (Unity's "Clear on Play", `clear_logs`) in between does not
+/// hide entries. Each poll re-reads the last entry it saw as an anchor; a
+/// lower `totalCaptured` or a missing anchor means the console was reset, and
+/// everything now in it is new.
+#[derive(Debug)]
+pub struct ConsoleWatch {
+    seen: u64,
+    anchor: Option<Value>,
+    entries: Vec<Value>,
+}
+
+impl ConsoleWatch {
+    /// Starts at the current end of the console.
+    pub async fn start(config: &RuntimeConfig) -> Result<Self> {
+        let mut client = connect(config).await?;
+        let result = read_detailed(&mut client, 1).await?;
+        Ok(Self {
+            seen: total_captured(&result),
+            anchor: logs(&result).pop(),
+            entries: Vec::new(),
+        })
+    }
+
+    /// Reads the entries logged since the previous poll, oldest first.
+    pub async fn poll(&mut self, config: &RuntimeConfig) -> Result<()> {
+        let mut client = connect(config).await?;
+        let mut reset = false;
+        let mut attempts = 0;
+        loop {
+            let current = console_total(&mut client).await?;
+            let anchored = !reset && self.anchor.is_some() && current >= self.seen;
+            let count = if anchored {
+                current - self.seen + 1
+            } else {
+                current
+            };
+            if count == 0 {
+                self.seen = 0;
+                self.anchor = None;
+                return Ok(());
+            }
+            let result = read_detailed(&mut client, count).await?;
+            attempts += 1;
+            // Entries logged between the two reads shift the newest-N window;
+            // read again so none are skipped or counted twice.
+            if total_captured(&result) != current && attempts < READ_ATTEMPTS {
+                continue;
+            }
+            let mut fresh = logs(&result);
+            if anchored {
+                let anchor = self.anchor.as_ref().expect("anchored polls have an anchor");
+                if !fresh.first().is_some_and(|entry| same_entry(entry, anchor)) {
+                    reset = true;
+                    continue;
+                }
+                fresh.remove(0);
+            }
+            self.seen = total_captured(&result);
+            if let Some(last) = fresh.last() {
+                self.anchor = Some(last.clone());
+            }
+            self.entries.extend(fresh);
+            return Ok(());
+        }
+    }
+
+    pub fn into_entries(self) -> Vec<Value> {
+        self.entries
+    }
+}
+
+/// Entries carry no id and their timestamp is the read time, so identity is
+/// the type, message and stack trace.
+fn same_entry(left: &Value, right: &Value) -> bool {
+    ["logType", "message", "stackTrace"]
+        .iter()
+        .all(|key| left.get(key) == right.get(key))
+}
+
+async fn read_detailed(client: &mut UnityClient, count: u64) -> Result<Value> {
+    read_console(
+        client,
+        &json!({
+            "count": count,
+            "logTypes": ["All"],
+            "includeStackTrace": true,
+            "format": "detailed",
+            "sortOrder": "oldest",
+        }),
+    )
+    .await
+}
+
+fn logs(result: &Value) -> Vec<Value> {
+    result
+        .get("logs")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default()
+}
+
+async fn console_total(client: &mut UnityClient) -> Result<u64> {
+    let result = read_console(client, &json!({ "count": 0, "logTypes": ["All"] })).await?;
+    Ok(total_captured(&result))
+}
+
+/// Runs the wrapped command against the same editor and returns its exit
+/// code, polling `watch` while it runs so console clears are noticed.
+pub async fn run_wrapped(
+    config: &RuntimeConfig,
+    command: &[String],
+    watch: &mut ConsoleWatch,
+) -> Result<i32> {
+    let Some((program, args)) = command.split_first() else {
+        bail!("Nothing to run; pass the command after `--`");
+    };
+    let mut child = Command::new(program);
+    child
+        .args(args)
+        .env("UNITY_CLI_HOST", &config.host)
+        .env("UNITY_CLI_PORT", config.port.to_string());
+    let program = program.clone();
+    let mut running = tokio::task::spawn_blocking(move || child.status());
+    let status = loop {
+        tokio::select! {
+            status = &mut running => break status,
+            _ = sleep(Duration::from_millis(WATCH_INTERVAL_MS)) => {
+                // The bridge may be reloading; the final poll reports errors.
+                let _ = watch.poll(config).await;
+            }
+        }
+    };
+    let status = status
+        .context("Wrapped command panicked")?
+        .with_context(|| format!("Failed to run `{program}`"))?;
+    watch.poll(config).await?;
+    Ok(status.code().unwrap_or(1))
+}
+
+/// Reads an allowlist file: one regex per line, `#` comments and blank lines
+/// ignored.
+pub fn load_allowlist(path: &Path) -> Result<Vec<Regex>> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read allowlist: {}", path.display()))?;
+    raw.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            Regex::new(line)
+                .with_context(|| format!("Invalid regex `{line}` in allowlist {}", path.display()))
+        })
+        .collect()
+}
+
+fn total_captured(result: &Value) -> u64 {
+    result
+        .get("totalCaptured")
+        .and_then(Value::as_u64)
+        .unwrap_or(0)
+}
+
+async fn connect(config: &RuntimeConfig) -> Result<UnityClient> {
+    UnityClient::connect(config).await.with_context(|| {
+        format!(
+            "Failed to connect to Unity at {}:{}",
+            config.host, config.port
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::{load_allowlist, ConsoleWatch, GuardOptions};
+    use crate::test_support::{config, spawn_editor};
+    use regex::Regex;
+    use serde_json::{json, Value};
+
+    static CONSOLE: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    fn log(messages: &[&str]) {
+        let mut console = CONSOLE.lock().expect("console lock");
+        console.extend(messages.iter().map(|message| message.to_string()));
+    }
+
+    fn read_console(request: &Value) -> Value {
+        let console = CONSOLE.lock().expect("console lock");
+        let count = request["params"]["count"].as_u64().unwrap_or(0) as usize;
+        let logs = console[console.len().saturating_sub(count)..]
+            .iter()
+            .map(|message| json!({ "logType": "Error", "message": message }))
+            .collect::<Vec<_>>();
+        json!({ "success": true, "logs": logs, "totalCaptured": console.len() })
+    }
+
+    #[test]
+    fn classify_applies_types_and_allowlist() {
+        let options = GuardOptions {
+            fail_on: vec!["Error".to_string(), "Exception".to_string()],
+            allow: vec![Regex::new("^Shader warning").expect("regex should compile")],
+        };
+        let entries = [
+            json!({ "logType": "Log", "message": "started" }),
+            json!({ "logType": "Warning", "message": "slow frame" }),
+            json!({ "logType": "Error", "message": "Shader warning in 'Hidden/Foo'" }),
+            json!({ "logType": "Exception", "message": "NullReferenceException" }),
+        ];
+        let (offending, ignored) = options.classify(&entries);
+        assert_eq!(offending, vec![entries[3].clone()]);
+        assert_eq!(ignored, 1);
+    }
+
+    #[test]
+    fn allowlist_file_skips_comments() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("allow.txt");
+        std::fs::write(&path, "# known noise\n\nShader .* not supported\n").expect("write");
+        let allow = load_allowlist(&path).expect("allowlist should load");
+        assert_eq!(allow.len(), 1);
+        assert!(allow[0].is_match("Shader Foo not supported"));
+
+        std::fs::write(&path, "Shader (\n").expect("write");
+        assert!(load_allowlist(&path).is_err());
+    }
+
+    #[tokio::test]
+    async fn console_watch_keeps_entries_across_a_clear() {
+        log(&["before one", "before two"]);
+        let (port, server) = spawn_editor(read_console).await;
+        let config = config(port);
+
+        let mut watch = ConsoleWatch::start(&config)
+            .await
+            .expect("watch should start");
+        log(&["during one"]);
+        watch.poll(&config).await.expect("poll should succeed");
+        // Cleared and refilled past the old count: a plain delta would miss
+        // the first entries after the clear.
+        CONSOLE.lock().expect("console lock").clear();
+        log(&["after one", "after two", "after three", "after four"]);
+        watch.poll(&config).await.expect("poll should succeed");
+        watch.poll(&config).await.expect("poll should succeed");
+        server.abort();
+
+        let messages = watch
+            .into_entries()
+            .iter()
+            .map(|entry| entry["message"].as_str().unwrap_or("").to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [
+                "during one",
+                "after one",
+                "after two",
+                "after three",
+                "after four"
+            ]
+        );
+    }
+}
@@ -1,17 +1,53 @@
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::io::Write;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use serde::{Deserialize, Serialize};
-use tokio::net::TcpStream;
+use serde_json::{json, Value};
+use tokio::task::JoinSet;
 use tokio::time::timeout;
 
+use crate::config::RuntimeConfig;
+use crate::transport::UnityClient;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct InstanceRecord {
     id: String,
+    /// Friendly name accepted wherever an instance id is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
     host: String,
     port: u16,
+    /// Last project root reported by the editor through `get_editor_info`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    project_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    unity_version: Option<String>,
+    /// Editor PID from the project's `Library/EditorInstance.json`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pid: Option<u32>,
+    /// Unix time of the last probe that reached the editor.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_seen_at: Option<u64>,
+}
+
+impl InstanceRecord {
+    fn new(host: &str, port: u16) -> Self {
+        Self {
+            id: format!("{host}:{port}"),
+            name: None,
+            host: host.to_string(),
+            port,
+            project_path: None,
+            unity_version: None,
+            pid: None,
+            last_seen_at: None,
+        }
+    }
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -22,14 +58,57 @@ struct Registry {
     entries: Vec<InstanceRecord>,
 }
 
+impl Registry {
+    /// Index of the entry whose id or name is `target`.
+    fn position(&self, target: &str) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|entry| entry.id == target || entry.name.as_deref() == Some(target))
+    }
+
+    fn summary(&self, entry: &InstanceRecord) -> InstanceSummary {
+        InstanceSummary {
+            id: entry.id.clone(),
+            name: entry.name.clone(),
+            host: entry.host.clone(),
+            port: entry.port,
+            active: self.active_id.as_deref() == Some(&entry.id),
+        }
+    }
+
+    fn validate_name(&self, name: &str, own_id: &str) -> Result<()> {
+        if name.is_empty() || name.contains(':') || name.chars().any(char::is_whitespace) {
+            bail!("Invalid instance name `{name}`: names must be non-empty and contain no `:` or spaces");
+        }
+        if let Some(other) = self
+            .entries
+            .iter()
+            .find(|entry| entry.id != own_id && entry.name.as_deref() == Some(name))
+        {
+            bail!("Instance name `{name}` is already used by {}", other.id);
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct InstanceStatus {
     pub id: String,
+    pub name: Option<String>,
     pub host: String,
     pub port: u16,
+    /// `up`, `busy`, `compiling`, `incompatible` or `down`.
     pub status: String,
+    /// Round trip of the framed `ping`, when the bridge answered.
+    pub latency_ms: Option<u64>,
+    pub bridge_version: Option<String>,
     pub last_checked_at: String,
     pub active: bool,
+    pub project_path: Option<String>,
+    pub unity_version: Option<String>,
+    pub pid: Option<u32>,
+    /// Down, and the editor process recorded for it is no longer running.
+    pub stale: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -38,91 +117,594 @@ pub struct SetActiveResult {
     pub previous_id: Option<String>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct InstanceSummary {
+    pub id: String,
+    pub name: Option<String>,
+    pub host: String,
+    pub port: u16,
+    pub active: bool,
+}
+
+/// One line of the append-only activation log.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub at: String,
+    pub active_id: String,
+    #[serde(default)]
+    pub previous_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RegistrySummary {
+    pub path: String,
+    pub active_id: Option<String>,
+    pub entries: usize,
+}
+
+pub fn registry_summary() -> Result<RegistrySummary> {
+    let path = registry_path()?;
+    let registry = load_registry()?;
+    Ok(RegistrySummary {
+        path: path.to_string_lossy().to_string(),
+        active_id: registry.active_id,
+        entries: registry.entries.len(),
+    })
+}
+
+/// Probes registered instances (plus `ports`) concurrently, and registers any
+/// port in `scan` that answers the bridge protocol.
+///
+/// Probing runs on a snapshot without holding the registry lock; the results
+/// are merged into the registry as it is when they are written back.
 pub async fn list_instances(
     host: &str,
     ports: &[u16],
+    scan: &[u16],
     timeout_ms: u64,
 ) -> Result<Vec<InstanceStatus>> {
-    let mut registry = load_registry()?;
-
-    for port in ports {
+    let snapshot = load_registry()?;
+    let timeout = Duration::from_millis(timeout_ms);
+    let registered = |port: &u16| {
         let id = format!("{host}:{port}");
-        if registry.entries.iter().all(|entry| entry.id != id) {
-            registry.entries.push(InstanceRecord {
-                id,
-                host: host.to_string(),
-                port: *port,
-            });
+        snapshot.entries.iter().any(|entry| entry.id == id)
+    };
+
+    let mut additions = ports
+        .iter()
+        .filter(|port| !registered(port))
+        .map(|port| (host.to_string(), *port))
+        .collect::<Vec<_>>();
+    let mut probes = HashMap::new();
+    let unregistered = scan
+        .iter()
+        .filter(|port| !registered(port) && !ports.contains(port))
+        .map(|port| (host.to_string(), *port))
+        .collect::<Vec<_>>();
+    for (target, probe) in probe_all(unregistered, timeout).await {
+        if probe.bridge {
+            additions.push(target.clone());
+            probes.insert(target, probe);
         }
     }
-
-    if registry.entries.is_empty() {
-        registry.entries.push(InstanceRecord {
-            id: format!("{}:{}", host, 6400),
-            host: host.to_string(),
-            port: 6400,
-        });
+    if snapshot.entries.is_empty() && additions.is_empty() {
+        additions.push((host.to_string(), 6400));
     }
 
-    registry.entries.sort_by(|a, b| a.id.cmp(&b.id));
+    let pending = snapshot
+        .entries
+        .iter()
+        .map(|entry| (entry.host.clone(), entry.port))
+        .chain(additions.iter().cloned())
+        .filter(|target| !probes.contains_key(target))
+        .collect();
+    probes.extend(probe_all(pending, timeout).await);
 
-    let mut statuses = Vec::with_capacity(registry.entries.len());
-    let timeout = Duration::from_millis(timeout_ms);
     let checked_at = unix_timestamp();
+    let seen_at = unix_seconds();
+    update_registry(|registry| {
+        for (host, port) in &additions {
+            let id = format!("{host}:{port}");
+            if registry.entries.iter().all(|entry| entry.id != id) {
+                registry.entries.push(InstanceRecord::new(host, *port));
+            }
+        }
+        registry.entries.sort_by(|a, b| a.id.cmp(&b.id));
 
-    for entry in &registry.entries {
-        let up = can_connect(&entry.host, entry.port, timeout).await;
-        statuses.push(InstanceStatus {
-            id: entry.id.clone(),
-            host: entry.host.clone(),
-            port: entry.port,
-            status: if up {
-                "up".to_string()
-            } else {
-                "down".to_string()
-            },
-            last_checked_at: checked_at.clone(),
-            active: registry.active_id.as_deref() == Some(&entry.id),
-        });
+        let mut statuses = Vec::with_capacity(registry.entries.len());
+        for entry in &mut registry.entries {
+            // Entries registered by another process meanwhile were not probed.
+            let Some(probe) = probes.remove(&(entry.host.clone(), entry.port)) else {
+                continue;
+            };
+            if let Some(facts) = probe.facts {
+                entry.project_path = facts.project_path.or(entry.project_path.take());
+                entry.unity_version = facts.unity_version.or(entry.unity_version.take());
+            }
+            if let Some(pid) = entry.project_path.as_deref().and_then(read_editor_pid) {
+                entry.pid = Some(pid);
+            }
+            if probe.health != Health::Down {
+                entry.last_seen_at = Some(seen_at);
+            }
+            let stale = probe.health == Health::Down && editor_exited(entry.pid);
+            statuses.push(InstanceStatus {
+                id: entry.id.clone(),
+                name: entry.name.clone(),
+                host: entry.host.clone(),
+                port: entry.port,
+                status: probe.health.as_str().to_string(),
+                latency_ms: probe.latency_ms,
+                bridge_version: probe.bridge_version,
+                last_checked_at: checked_at.clone(),
+                active: registry.active_id.as_deref() == Some(&entry.id),
+                project_path: entry.project_path.clone(),
+                unity_version: entry.unity_version.clone(),
+                pid: entry.pid,
+                stale,
+            });
+        }
+        Ok(statuses)
+    })
+}
+
+pub async fn set_active_instance(target: &str, timeout_ms: u64) -> Result<SetActiveResult> {
+    let snapshot = load_registry()?;
+    let record = match snapshot.position(target) {
+        Some(index) => snapshot.entries[index].clone(),
+        None => {
+            let (host, port) = parse_id(target).with_context(|| {
+                format!("Unknown instance `{target}`; use a registered name or a host:port id")
+            })?;
+            InstanceRecord {
+                id: target.to_string(),
+                ..InstanceRecord::new(&host, port)
+            }
+        }
+    };
+    let id = record.id.clone();
+
+    let probe = probe_editor(&record.host, record.port, Duration::from_millis(timeout_ms)).await;
+    match probe.health {
+        Health::Up | Health::Compiling => {}
+        Health::Down => return Err(anyhow!("Instance unreachable: {id}")),
+        other => return Err(anyhow!("Instance {id} is {}", other.as_str())),
     }
 
-    save_registry(&registry)?;
-    Ok(statuses)
+    update_registry(|registry| {
+        if registry.entries.iter().all(|entry| entry.id != id) {
+            registry.entries.push(record.clone());
+        }
+        let previous_id = registry.active_id.replace(id.clone());
+        append_history(&HistoryEntry {
+            at: unix_timestamp(),
+            active_id: id.clone(),
+            previous_id: previous_id.clone(),
+            name: record.name.clone(),
+        })?;
+        Ok(SetActiveResult {
+            active_id: id.clone(),
+            previous_id,
+        })
+    })
 }
 
-pub async fn set_active_instance(id: &str, timeout_ms: u64) -> Result<SetActiveResult> {
-    let mut registry = load_registry()?;
+pub fn add_instance(id: &str, name: Option<&str>) -> Result<InstanceSummary> {
+    let (host, port) = parse_id(id)?;
+    update_registry(|registry| {
+        if registry.entries.iter().any(|entry| entry.id == id) {
+            bail!("Instance {id} is already registered; use `instances rename` to name it");
+        }
+        if let Some(name) = name {
+            registry.validate_name(name, id)?;
+        }
 
-    if registry.entries.iter().all(|entry| entry.id != id) {
-        let (host, port) = parse_id(id)?;
-        registry.entries.push(InstanceRecord {
+        let record = InstanceRecord {
             id: id.to_string(),
-            host,
-            port,
-        });
-    }
+            name: name.map(str::to_string),
+            ..InstanceRecord::new(&host, port)
+        };
+        let summary = registry.summary(&record);
+        registry.entries.push(record);
+        registry.entries.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(summary)
+    })
+}
+
+/// Removes an entry by id or name, clearing the active instance if it was the one removed.
+pub fn remove_instance(target: &str) -> Result<InstanceSummary> {
+    update_registry(|registry| {
+        let index = registry
+            .position(target)
+            .ok_or_else(|| anyhow!("Instance not found: {target}"))?;
+        let summary = registry.summary(&registry.entries[index]);
+        registry.entries.remove(index);
+        if summary.active {
+            registry.active_id = None;
+        }
+        Ok(summary)
+    })
+}
 
-    let target = registry
+pub fn rename_instance(target: &str, name: &str) -> Result<InstanceSummary> {
+    update_registry(|registry| {
+        let index = registry
+            .position(target)
+            .ok_or_else(|| anyhow!("Instance not found: {target}"))?;
+        registry.validate_name(name, &registry.entries[index].id)?;
+        registry.entries[index].name = Some(name.to_string());
+        Ok(registry.summary(&registry.entries[index]))
+    })
+}
+
+/// Removes entries whose health check reports `down`: all of them with
+/// `down`, or only those whose recorded editor process has exited with `stale`.
+pub async fn prune_instances(
+    down: bool,
+    stale: bool,
+    timeout_ms: u64,
+) -> Result<Vec<InstanceSummary>> {
+    let snapshot = load_registry()?;
+    let targets = snapshot
         .entries
         .iter()
-        .find(|entry| entry.id == id)
-        .ok_or_else(|| anyhow!("Instance not found: {id}"))?;
+        .map(|entry| (entry.host.clone(), entry.port))
+        .collect();
+    let selected = probe_all(targets, Duration::from_millis(timeout_ms))
+        .await
+        .into_iter()
+        .filter(|(_, probe)| probe.health == Health::Down)
+        .map(|(target, _)| target)
+        .filter(|(host, port)| {
+            let exited = || {
+                snapshot
+                    .entries
+                    .iter()
+                    .find(|entry| &entry.host == host && entry.port == *port)
+                    .is_some_and(|entry| {
+                        let pid = entry
+                            .project_path
+                            .as_deref()
+                            .and_then(read_editor_pid)
+                            .or(entry.pid);
+                        editor_exited(pid)
+                    })
+            };
+            down || (stale && exited())
+        })
+        .collect::<Vec<_>>();
+
+    update_registry(|registry| {
+        let (removed, kept) = std::mem::take(&mut registry.entries)
+            .into_iter()
+            .partition::<Vec<_>, _>(|entry| selected.contains(&(entry.host.clone(), entry.port)));
+        registry.entries = kept;
+        let removed = removed
+            .iter()
+            .map(|entry| registry.summary(entry))
+            .collect::<Vec<_>>();
+        if removed.iter().any(|entry| entry.active) {
+            registry.active_id = None;
+        }
+        Ok(removed)
+    })
+}
 
-    let ok = can_connect(&target.host, target.port, Duration::from_millis(timeout_ms)).await;
-    if !ok {
-        return Err(anyhow!("Instance unreachable: {id}"));
+/// Returns the activation log, oldest first; `limit` keeps the most recent entries.
+pub fn instance_history(limit: Option<usize>) -> Result<Vec<HistoryEntry>> {
+    let path = history_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let raw = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read history file: {}", path.display()))?;
+    let mut entries = raw
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Failed to parse history file: {}", path.display()))
+        })
+        .collect::<Result<Vec<HistoryEntry>>>()?;
+    if let Some(limit) = limit {
+        entries.drain(..entries.len().saturating_sub(limit));
     }
+    Ok(entries)
+}
 
-    let previous_id = registry.active_id.clone();
-    registry.active_id = Some(id.to_string());
-    save_registry(&registry)?;
+/// Resolves `--instance`: a registered name or id, or any `host:port`.
+pub fn resolve_instance(target: &str) -> Result<(String, u16)> {
+    let registry = load_registry()?;
+    match registry.position(target) {
+        Some(index) => {
+            let entry = &registry.entries[index];
+            Ok((entry.host.clone(), entry.port))
+        }
+        None => parse_id(target).with_context(|| {
+            format!("Unknown instance `{target}`; use a registered name or a host:port id")
+        }),
+    }
+}
+
+/// Picks the registered instance to talk to when no host/port was given:
+/// the live entry serving `project_root` that was seen most recently,
+/// otherwise the active instance. Entries whose editor process has exited or
+/// whose port refuses connections are skipped, so an old registration left
+/// behind by a restarted editor does not win.
+pub fn preferred_instance(project_root: Option<&Path>) -> Option<(String, u16)> {
+    let registry = load_registry().ok()?;
+    let mut matching = project_root
+        .map(|root| {
+            registry
+                .entries
+                .iter()
+                .filter(|entry| {
+                    entry
+                        .project_path
+                        .as_deref()
+                        .is_some_and(|path| same_path(Path::new(path), root))
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    matching.sort_by_key(|entry| std::cmp::Reverse(entry.last_seen_at));
+    let live = matching
+        .into_iter()
+        .find(|entry| !editor_exited(entry.pid) && accepts_connection(&entry.host, entry.port));
+    let entry = live.or_else(|| {
+        let active_id = registry.active_id.as_deref()?;
+        registry.entries.iter().find(|entry| entry.id == active_id)
+    })?;
+    Some((entry.host.clone(), entry.port))
+}
 
-    Ok(SetActiveResult {
-        active_id: id.to_string(),
-        previous_id,
+/// Cheap liveness check for `preferred_instance`: something accepts TCP
+/// connections on the port.
+fn accepts_connection(host: &str, port: u16) -> bool {
+    let Ok(addrs) = (host, port).to_socket_addrs() else {
+        return false;
+    };
+    addrs.into_iter().any(|addr| {
+        TcpStream::connect_timeout(&addr, Duration::from_millis(LIVENESS_TIMEOUT_MS)).is_ok()
     })
 }
 
+fn same_path(a: &Path, b: &Path) -> bool {
+    let normalize = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    normalize(a) == normalize(b)
+}
+
+/// Connect timeout for the liveness check in `preferred_instance`.
+const LIVENESS_TIMEOUT_MS: u64 = 200;
+
+/// Upper bound on simultaneous probe connections, so wide `--scan` ranges do
+/// not exhaust file descriptors.
+const MAX_CONCURRENT_PROBES: usize = 64;
+
+/// Result of a protocol-level health check.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Health {
+    /// `ping` answered and the bridge version is compatible.
+    Up,
+    /// The port accepted a connection but the command queue did not answer in time.
+    Busy,
+    /// The editor answered but is compiling scripts.
+    Compiling,
+    /// The response was not a bridge envelope, or the bridge version does not
+    /// match this CLI.
+    Incompatible,
+    /// Nothing accepted the connection, or it was closed without a response.
+    #[default]
+    Down,
+}
+
+impl Health {
+    fn as_str(self) -> &'static str {
+        match self {
+            Health::Up => "up",
+            Health::Busy => "busy",
+            Health::Compiling => "compiling",
+            Health::Incompatible => "incompatible",
+            Health::Down => "down",
+        }
+    }
+}
+
+#[derive(Default)]
+struct Probe {
+    health: Health,
+    latency_ms: Option<u64>,
+    bridge_version: Option<String>,
+    /// Something on the port answered with a bridge response envelope.
+    bridge: bool,
+    facts: Option<EditorFacts>,
+}
+
+struct EditorFacts {
+    project_path: Option<String>,
+    unity_version: Option<String>,
+}
+
+async fn probe_all(targets: Vec<(String, u16)>, timeout: Duration) -> Vec<((String, u16), Probe)> {
+    let mut results = Vec::with_capacity(targets.len());
+    let mut tasks = JoinSet::new();
+    for (host, port) in targets {
+        if tasks.len() >= MAX_CONCURRENT_PROBES {
+            if let Some(Ok(result)) = tasks.join_next().await {
+                results.push(result);
+            }
+        }
+        tasks.spawn(async move {
+            let probe = probe_editor(&host, port, timeout).await;
+            ((host, port), probe)
+        });
+    }
+    while let Some(result) = tasks.join_next().await {
+        if let Ok(result) = result {
+            results.push(result);
+        }
+    }
+    results
+}
+
+/// Sends a framed `ping` within `deadline` and classifies the instance; for
+/// live editors, also collects the compile state and editor facts.
+async fn probe_editor(host: &str, port: u16, deadline: Duration) -> Probe {
+    let Ok(Ok(mut client)) = timeout(
+        deadline,
+        UnityClient::connect(&RuntimeConfig {
+            host: host.to_string(),
+            port,
+            // Leave the deadline to the outer timeouts, so a silent queue is
+            // reported as busy rather than as a transport error.
+            timeout: deadline * 2,
+        }),
+    )
+    .await
+    else {
+        return Probe::default();
+    };
+
+    let started = Instant::now();
+    let envelope = match timeout(deadline, client.call_tool_raw("ping", json!({}))).await {
+        Err(_) => {
+            return Probe {
+                health: Health::Busy,
+                ..Probe::default()
+            }
+        }
+        Ok(Err(error)) if error.chain().any(|cause| cause.is::<std::io::Error>()) => {
+            return Probe::default();
+        }
+        Ok(Err(_)) => {
+            return Probe {
+                health: Health::Incompatible,
+                ..Probe::default()
+            }
+        }
+        Ok(Ok(envelope)) => envelope,
+    };
+    let latency_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+    let bridge = envelope.get("status").is_some() || envelope.get("result").is_some();
+    let bridge_version = envelope
+        .pointer("/editorState/version")
+        .or_else(|| envelope.get("version"))
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let mut probe = Probe {
+        health: Health::Up,
+        latency_ms: Some(latency_ms),
+        bridge_version,
+        bridge,
+        facts: None,
+    };
+
+    let status = envelope.get("status").and_then(Value::as_str);
+    let code = envelope.get("code").and_then(Value::as_str);
+    let compatible = probe
+        .bridge_version
+        .as_deref()
+        .is_none_or(|version| is_compatible_version(version, env!("CARGO_PKG_VERSION")));
+    probe.health = if bridge && code == Some("COMPILING") {
+        Health::Compiling
+    } else if !bridge || status == Some("error") || !compatible {
+        Health::Incompatible
+    } else {
+        Health::Up
+    };
+    if probe.health != Health::Up {
+        return probe;
+    }
+
+    if let Ok(Ok(state)) = timeout(
+        deadline,
+        client.call_tool("get_compilation_state", json!({})),
+    )
+    .await
+    {
+        if state.get("isCompiling").and_then(Value::as_bool) == Some(true) {
+            probe.health = Health::Compiling;
+        }
+    }
+
+    if let Ok(Ok(info)) = timeout(deadline, client.call_tool("get_editor_info", json!({}))).await {
+        let text = |pointer: &str| {
+            info.pointer(pointer)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        };
+        probe.facts = Some(EditorFacts {
+            project_path: text("/projectRoot"),
+            unity_version: text("/unity/unityVersion"),
+        });
+    }
+    probe
+}
+
+/// Bridge and CLI share a protocol when their semver-compatible parts match:
+/// the major version, or the minor version while major is 0. Versions that do
+/// not parse (e.g. `unknown`) are assumed compatible.
+fn is_compatible_version(bridge: &str, cli: &str) -> bool {
+    let parse = |version: &str| -> Option<(u64, u64)> {
+        let mut parts = version.trim().trim_start_matches('v').split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        Some((major, minor))
+    };
+    match (parse(bridge), parse(cli)) {
+        (Some((0, bridge_minor)), Some((0, cli_minor))) => bridge_minor == cli_minor,
+        (Some((bridge_major, _)), Some((cli_major, _))) => bridge_major == cli_major,
+        _ => true,
+    }
+}
+
+/// Reads the editor PID Unity writes to `Library/EditorInstance.json` while a
+/// project is open.
+fn read_editor_pid(project_path: &str) -> Option<u32> {
+    let path = Path::new(project_path)
+        .join("Library")
+        .join("EditorInstance.json");
+    let raw = fs::read_to_string(path).ok()?;
+    let value: Value = serde_json::from_str(&raw).ok()?;
+    value
+        .get("process_id")
+        .and_then(Value::as_u64)
+        .and_then(|pid| u32::try_from(pid).ok())
+}
+
+/// Whether the editor process `pid` is known to have exited.
+fn editor_exited(pid: Option<u32>) -> bool {
+    pid.is_some_and(|pid| process_alive(pid) == Some(false))
+}
+
+/// Whether `pid` is still running; `None` when it cannot be determined.
+#[cfg(target_os = "linux")]
+fn process_alive(pid: u32) -> Option<bool> {
+    Some(Path::new("/proc").join(pid.to_string()).exists())
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn process_alive(pid: u32) -> Option<bool> {
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(std::process::Stdio::null())
+        .status()
+        .ok()
+        .map(|status| status.success())
+}
+
+#[cfg(windows)]
+fn process_alive(pid: u32) -> Option<bool> {
+    let output = std::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {pid}"), "/NH"])
+        .output()
+        .ok()?;
+    Some(String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+}
+
 fn parse_id(id: &str) -> Result<(String, u16)> {
     let (host, port_str) = id
         .split_once(':')
@@ -136,13 +718,6 @@ fn parse_id(id: &str) -> Result<(String, u16)> {
     Ok((host.to_string(), port))
 }
 
-async fn can_connect(host: &str, port: u16, timeout_duration: Duration) -> bool {
-    matches!(
-        timeout(timeout_duration, TcpStream::connect((host, port))).await,
-        Ok(Ok(_))
-    )
-}
-
 fn registry_path() -> Result<PathBuf> {
     if let Ok(raw_path) = std::env::var("UNITY_CLI_REGISTRY_PATH") {
         let trimmed = raw_path.trim();
@@ -167,32 +742,133 @@ fn registry_path() -> Result<PathBuf> {
     Ok(dir.join("instances.json"))
 }
 
+/// Reads the registry for lookups. A registry that fails to parse is left
+/// untouched and read as empty, with a warning; only `update_registry`
+/// recovers it, under the lock.
 fn load_registry() -> Result<Registry> {
     let path = registry_path()?;
+    match read_registry(&path)? {
+        Ok(registry) => Ok(registry),
+        Err(error) => {
+            eprintln!(
+                "Warning: instance registry {} is corrupt ({error}); ignoring it until the next update",
+                path.display()
+            );
+            Ok(Registry::default())
+        }
+    }
+}
+
+/// Reads and parses the registry file; a missing file is an empty registry.
+fn read_registry(path: &Path) -> Result<std::result::Result<Registry, serde_json::Error>> {
     if !path.exists() {
-        return Ok(Registry::default());
+        return Ok(Ok(Registry::default()));
     }
 
-    let raw = fs::read_to_string(&path)
+    let raw = fs::read_to_string(path)
         .with_context(|| format!("Failed to read registry file: {}", path.display()))?;
 
-    let mut registry: Registry = serde_json::from_str(&raw)
-        .with_context(|| format!("Failed to parse registry file: {}", path.display()))?;
-
-    for entry in &mut registry.entries {
-        if entry.id.trim().is_empty() {
-            entry.id = format!("{}:{}", entry.host, entry.port);
+    Ok(serde_json::from_str(&raw).map(|mut registry: Registry| {
+        for entry in &mut registry.entries {
+            if entry.id.trim().is_empty() {
+                entry.id = format!("{}:{}", entry.host, entry.port);
+            }
         }
-    }
+        registry
+    }))
+}
 
-    Ok(registry)
+/// Moves a corrupt registry aside to `.corrupt-<nanos>` (with a counter if
+/// that name is taken) and returns the backup path.
+fn back_up_corrupt_registry(path: &Path) -> Result<PathBuf> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or(0);
+    let mut backup = path.with_extension(format!("json.corrupt-{nanos}"));
+    let mut counter = 1;
+    while backup.exists() {
+        backup = path.with_extension(format!("json.corrupt-{nanos}-{counter}"));
+        counter += 1;
+    }
+    fs::rename(path, &backup).with_context(|| {
+        format!(
+            "Failed to back up corrupt registry file: {}",
+            path.display()
+        )
+    })?;
+    Ok(backup)
 }
 
+/// Writes the registry to a temporary file and renames it into place, so
+/// readers never observe a partially written file.
 fn save_registry(registry: &Registry) -> Result<()> {
     let path = registry_path()?;
     let content = serde_json::to_string_pretty(registry)?;
-    fs::write(&path, content)
-        .with_context(|| format!("Failed to write registry file: {}", path.display()))
+    let temp = path.with_extension(format!("json.tmp-{}", std::process::id()));
+    fs::write(&temp, content)
+        .with_context(|| format!("Failed to write registry file: {}", temp.display()))?;
+    fs::rename(&temp, &path)
+        .with_context(|| format!("Failed to replace registry file: {}", path.display()))
+}
+
+/// Runs a read-modify-write of the registry under an exclusive advisory lock
+/// on `<registry>.lock`, saving it only when `update` succeeds. A corrupt
+/// registry is backed up and replaced here, where no other writer can race.
+fn update_registry<T>(update: impl FnOnce(&mut Registry) -> Result<T>) -> Result<T> {
+    let path = registry_path()?;
+    let lock_path = path.with_extension("json.lock");
+    let lock = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .with_context(|| format!("Failed to open registry lock: {}", lock_path.display()))?;
+    lock.lock()
+        .with_context(|| format!("Failed to lock registry: {}", lock_path.display()))?;
+
+    let mut registry = match read_registry(&path)? {
+        Ok(registry) => registry,
+        Err(error) => {
+            let backup = back_up_corrupt_registry(&path)?;
+            eprintln!(
+                "Warning: instance registry {} was corrupt ({error}); moved it to {} and started a new one",
+                path.display(),
+                backup.display()
+            );
+            Registry::default()
+        }
+    };
+    let value = update(&mut registry)?;
+    save_registry(&registry)?;
+    Ok(value)
+}
+
+fn history_path() -> Result<PathBuf> {
+    let registry = registry_path()?;
+    let stem = registry
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "instances".to_string());
+    Ok(registry.with_file_name(format!("{stem}-history.jsonl")))
+}
+
+fn append_history(entry: &HistoryEntry) -> Result<()> {
+    let path = history_path()?;
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open history file: {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)
+        .with_context(|| format!("Failed to write history file: {}", path.display()))
+}
+
+fn unix_seconds() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
 }
 
 fn unix_timestamp() -> String {
@@ -204,25 +880,33 @@ fn unix_timestamp() -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{list_instances, parse_id, set_active_instance};
-    use std::path::PathBuf;
-    use std::sync::{Mutex, OnceLock};
-    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    use super::{
+        add_instance, history_path, instance_history, is_compatible_version, list_instances,
+        parse_id, preferred_instance, probe_editor, prune_instances, registry_summary,
+        remove_instance, rename_instance, resolve_instance, set_active_instance, Health,
+    };
+    use crate::test_support::{registry_lock, spawn_bridge, temp_registry_path};
+    use serde_json::{json, Value};
+    use std::path::Path;
+    use std::sync::OnceLock;
+    use std::time::Duration;
     use tokio::net::TcpListener;
 
-    fn env_lock() -> &'static Mutex<()> {
-        static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
-        LOCK.get_or_init(|| Mutex::new(()))
+    /// Removes the registry with its lock and history files.
+    fn remove_registry_files(registry_path: &Path) {
+        let _ = std::fs::remove_file(registry_path);
+        let _ = std::fs::remove_file(registry_path.with_extension("json.lock"));
+        if let Ok(history) = history_path() {
+            let _ = std::fs::remove_file(history);
+        }
     }
 
-    fn temp_registry_path(label: &str) -> PathBuf {
-        let nanos = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or(Duration::from_secs(0))
-            .as_nanos();
-        let mut path = std::env::temp_dir();
-        path.push(format!("unity-cli-{label}-{nanos}.json"));
-        path
+    fn success(result: Value) -> Value {
+        json!({
+            "status": "success",
+            "result": result,
+            "editorState": { "isPlaying": false, "version": env!("CARGO_PKG_VERSION") }
+        })
     }
 
     #[test]
@@ -237,37 +921,86 @@ mod tests {
 
     #[tokio::test]
     #[allow(clippy::await_holding_lock)]
-    async fn list_instances_reports_up_for_reachable_port() {
-        let _guard = env_lock().lock().expect("lock should succeed");
+    async fn list_instances_reports_up_for_answering_bridge() {
+        let _guard = registry_lock().lock().expect("lock should succeed");
         let registry_path = temp_registry_path("instances-up");
         std::env::set_var("UNITY_CLI_REGISTRY_PATH", &registry_path);
 
-        let listener = TcpListener::bind(("127.0.0.1", 0))
+        let (port, server) = spawn_bridge(|_| success(json!({ "message": "pong" }))).await;
+
+        let statuses = list_instances("127.0.0.1", &[port], &[], 300)
+            .await
+            .expect("list should succeed");
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].status, "up");
+        assert!(statuses[0].latency_ms.is_some());
+        assert_eq!(
+            statuses[0].bridge_version.as_deref(),
+            Some(env!("CARGO_PKG_VERSION"))
+        );
+
+        server.abort();
+        std::env::remove_var("UNITY_CLI_REGISTRY_PATH");
+        remove_registry_files(&registry_path);
+    }
+
+    #[tokio::test]
+    async fn probe_classifies_unhealthy_editors() {
+        let deadline = Duration::from_millis(300);
+
+        let (silent, silent_server) = spawn_bridge(|_| Value::Null).await;
+        let (compiling, compiling_server) =
+            spawn_bridge(|request| match request["type"].as_str() {
+                Some("get_compilation_state") => success(json!({ "isCompiling": true })),
+                _ => success(json!({})),
+            })
+            .await;
+        let (outdated, outdated_server) = spawn_bridge(|_| {
+            json!({ "status": "success", "result": {}, "editorState": { "version": "99.0.0" } })
+        })
+        .await;
+        let half_dead = TcpListener::bind(("127.0.0.1", 0))
             .await
             .expect("listener should bind");
-        let port = listener
+        let half_dead_port = half_dead
             .local_addr()
             .expect("listener should have local addr")
             .port();
-        let accept_task = tokio::spawn(async move {
-            let _ = listener.accept().await;
+        let half_dead_server = tokio::spawn(async move {
+            while let Ok((socket, _)) = half_dead.accept().await {
+                drop(socket);
+            }
         });
 
-        let statuses = list_instances("127.0.0.1", &[port], 300)
-            .await
-            .expect("list should succeed");
-        assert_eq!(statuses.len(), 1);
-        assert_eq!(statuses[0].status, "up");
+        let health = |port| async move { probe_editor("127.0.0.1", port, deadline).await.health };
+        assert_eq!(health(silent).await, Health::Busy);
+        assert_eq!(health(compiling).await, Health::Compiling);
+        assert_eq!(health(outdated).await, Health::Incompatible);
+        assert_eq!(health(half_dead_port).await, Health::Down);
 
-        accept_task.abort();
-        std::env::remove_var("UNITY_CLI_REGISTRY_PATH");
-        let _ = std::fs::remove_file(&registry_path);
+        for server in [
+            silent_server,
+            compiling_server,
+            outdated_server,
+            half_dead_server,
+        ] {
+            server.abort();
+        }
+    }
+
+    #[test]
+    fn compatible_versions_follow_semver() {
+        assert!(is_compatible_version("0.1.3", "0.1.0"));
+        assert!(!is_compatible_version("0.2.0", "0.1.0"));
+        assert!(is_compatible_version("1.4.0", "1.0.2"));
+        assert!(!is_compatible_version("2.0.0", "1.9.9"));
+        assert!(is_compatible_version("unknown", "0.1.0"));
     }
 
     #[tokio::test]
     #[allow(clippy::await_holding_lock)]
     async fn set_active_fails_for_unreachable_instance() {
-        let _guard = env_lock().lock().expect("lock should succeed");
+        let _guard = registry_lock().lock().expect("lock should succeed");
         let registry_path = temp_registry_path("instances-down");
         std::env::set_var("UNITY_CLI_REGISTRY_PATH", &registry_path);
 
@@ -287,6 +1020,364 @@ mod tests {
         assert!(format!("{err:#}").contains("unreachable"));
 
         std::env::remove_var("UNITY_CLI_REGISTRY_PATH");
-        let _ = std::fs::remove_file(&registry_path);
+        remove_registry_files(&registry_path);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::await_holding_lock)]
+    async fn list_instances_records_project_and_selects_matching_instance() {
+        let _guard = registry_lock().lock().expect("lock should succeed");
+        let registry_path = temp_registry_path("instances-project");
+        std::env::set_var("UNITY_CLI_REGISTRY_PATH", &registry_path);
+
+        static PROJECT: OnceLock<String> = OnceLock::new();
+        let project = tempfile::tempdir().expect("temp dir should be created");
+        PROJECT
+            .set(project.path().to_string_lossy().to_string())
+            .expect("project should be set once");
+        let (port, server) = spawn_bridge(|request| match request["type"].as_str() {
+            Some("get_editor_info") => success(json!({
+                "projectRoot": PROJECT.get(),
+                "unity": { "unityVersion": "2022.3.10f1" }
+            })),
+            _ => success(json!({})),
+        })
+        .await;
+
+        let statuses = list_instances("127.0.0.1", &[port], &[], 500)
+            .await
+            .expect("list should succeed");
+        assert_eq!(statuses[0].status, "up");
+        assert_eq!(
+            statuses[0].project_path.as_deref(),
+            PROJECT.get().map(String::as_str)
+        );
+        assert_eq!(statuses[0].unity_version.as_deref(), Some("2022.3.10f1"));
+
+        assert_eq!(
+            preferred_instance(Some(project.path())),
+            Some(("127.0.0.1".to_string(), port))
+        );
+        let elsewhere = tempfile::tempdir().expect("temp dir should be created");
+        assert_eq!(preferred_instance(Some(elsewhere.path())), None);
+
+        server.abort();
+        std::env::remove_var("UNITY_CLI_REGISTRY_PATH");
+        remove_registry_files(&registry_path);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::await_holding_lock)]
+    async fn list_instances_scan_registers_only_bridge_ports() {
+        let _guard = registry_lock().lock().expect("lock should succeed");
+        let registry_path = temp_registry_path("instances-scan");
+        std::env::set_var("UNITY_CLI_REGISTRY_PATH", &registry_path);
+
+        let (port, server) =
+            spawn_bridge(|_| success(json!({ "unity": { "unityVersion": "6000.0.1f1" } }))).await;
+        let closed = TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .expect("listener should bind");
+        let closed_port = closed
+            .local_addr()
+            .expect("listener should have local addr")
+            .port();
+        drop(closed);
+
+        let statuses = list_instances("127.0.0.1", &[], &[port, closed_port], 500)
+            .await
+            .expect("scan should succeed");
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].id, format!("127.0.0.1:{port}"));
+        assert_eq!(statuses[0].status, "up");
+        assert_eq!(statuses[0].unity_version.as_deref(), Some("6000.0.1f1"));
+
+        server.abort();
+        std::env::remove_var("UNITY_CLI_REGISTRY_PATH");
+        remove_registry_files(&registry_path);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::await_holding_lock)]
+    async fn preferred_instance_skips_dead_registrations_for_the_project() {
+        let _guard = registry_lock().lock().expect("lock should succeed");
+        let registry_path = temp_registry_path("instances-preferred");
+        std::env::set_var("UNITY_CLI_REGISTRY_PATH", &registry_path);
+
+        let project = tempfile::tempdir().expect("temp dir should be created");
+        let (port, server) = spawn_bridge(|_| success(json!({}))).await;
+        let closed = TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .expect("listener should bind");
+        let dead_port = closed
+            .local_addr()
+            .expect("listener should have local addr")
+            .port();
+        drop(closed);
+        // The dead entry sorts first and was seen more recently.
+        let write_registry = |live_port: u16| {
+            std::fs::write(
+                &registry_path,
+                json!({
+                    "active_id": "127.0.0.1:3",
+                    "entries": [
+                        { "id": format!("127.0.0.1:{dead_port}"), "host": "127.0.0.1",
+                          "port": dead_port, "project_path": project.path(), "last_seen_at": 200 },
+                        { "id": format!("127.0.0.1:{live_port}"), "host": "127.0.0.1",
+                          "port": live_port, "project_path": project.path(), "last_seen_at": 100 },
+                        { "id": "127.0.0.1:3", "host": "127.0.0.1", "port": 3 }
+                    ]
+                })
+                .to_string(),
+            )
+            .expect("registry should be written");
+        };
+
+        write_registry(port);
+        assert_eq!(
+            preferred_instance(Some(project.path())),
+            Some(("127.0.0.1".to_string(), port))
+        );
+
+        // Without a live match, the active instance wins.
+        server.abort();
+        let _ = server.await;
+        write_registry(dead_port);
+        assert_eq!(
+            preferred_instance(Some(project.path())),
+            Some(("127.0.0.1".to_string(), 3))
+        );
+
+        std::env::remove_var("UNITY_CLI_REGISTRY_PATH");
+        remove_registry_files(&registry_path);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    #[allow(clippy::await_holding_lock)]
+    async fn list_instances_marks_entries_with_dead_editor_process_stale() {
+        let _guard = registry_lock().lock().expect("lock should succeed");
+        let registry_path = temp_registry_path("instances-stale");
+        std::env::set_var("UNITY_CLI_REGISTRY_PATH", &registry_path);
+
+        let mut child = std::process::Command::new("true")
+            .spawn()
+            .expect("child should spawn");
+        let dead_pid = child.id();
+        child.wait().expect("child should exit");
+
+        let project = tempfile::tempdir().expect("temp dir should be created");
+        std::fs::create_dir_all(project.path().join("Library")).expect("Library should be created");
+        std::fs::write(
+            project.path().join("Library/EditorInstance.json"),
+            json!({ "process_id": dead_pid, "version": "2022.3.10f1" }).to_string(),
+        )
+        .expect("EditorInstance.json should be written");
+        std::fs::write(
+            &registry_path,
+            json!({
+                "entries": [
+                    { "id": "127.0.0.1:1", "host": "127.0.0.1", "port": 1,
+                      "project_path": project.path() },
+                    { "id": "127.0.0.1:2", "host": "127.0.0.1", "port": 2,
+                      "pid": std::process::id() }
+                ]
+            })
+            .to_string(),
+        )
+        .expect("registry should be written");
+
+        let statuses = list_instances("127.0.0.1", &[], &[], 200)
+            .await
+            .expect("list should succeed");
+        assert_eq!(statuses[0].status, "down");
+        assert_eq!(statuses[0].pid, Some(dead_pid));
+        assert!(statuses[0].stale);
+        assert_eq!(statuses[1].pid, Some(std::process::id()));
+        assert!(!statuses[1].stale);
+
+        let removed = prune_instances(false, true, 200)
+            .await
+            .expect("prune should succeed");
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].id, "127.0.0.1:1");
+        assert_eq!(registry_summary().expect("summary should load").entries, 1);
+
+        std::env::remove_var("UNITY_CLI_REGISTRY_PATH");
+        remove_registry_files(&registry_path);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::await_holding_lock)]
+    async fn registry_management_names_removes_and_logs_activations() {
+        let _guard = registry_lock().lock().expect("lock should succeed");
+        let registry_path = temp_registry_path("instances-manage");
+        std::env::set_var("UNITY_CLI_REGISTRY_PATH", &registry_path);
+
+        let (port, server) = spawn_bridge(|_| success(json!({}))).await;
+        let id = format!("127.0.0.1:{port}");
+        let added = add_instance(&id, Some("client")).expect("add should succeed");
+        assert_eq!(added.name.as_deref(), Some("client"));
+        assert!(add_instance(&id, None).is_err());
+        assert!(add_instance("127.0.0.1:1", Some("client")).is_err());
+        assert!(add_instance("127.0.0.1:2", Some("bad name")).is_err());
+        assert_eq!(
+            resolve_instance("client").expect("name should resolve"),
+            ("127.0.0.1".to_string(), port)
+        );
+
+        let result = set_active_instance("client", 500)
+            .await
+            .expect("set-active by name should succeed");
+        assert_eq!(result.active_id, id);
+        let history = instance_history(None).expect("history should load");
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].active_id, id);
+        assert_eq!(history[0].name.as_deref(), Some("client"));
+        assert_eq!(history[0].previous_id, None);
+
+        rename_instance("client", "server").expect("rename should succeed");
+        assert!(resolve_instance("server").is_ok());
+        assert!(resolve_instance("client").is_err());
+
+        let removed = remove_instance("server").expect("remove should succeed");
+        assert!(removed.active);
+        let summary = registry_summary().expect("summary should load");
+        assert_eq!(summary.active_id, None);
+        assert_eq!(summary.entries, 0);
+        assert_eq!(
+            instance_history(None).expect("history should load").len(),
+            1
+        );
+
+        server.abort();
+        std::env::remove_var("UNITY_CLI_REGISTRY_PATH");
+        remove_registry_files(&registry_path);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::await_holding_lock)]
+    async fn prune_down_removes_only_unreachable_instances() {
+        let _guard = registry_lock().lock().expect("lock should succeed");
+        let registry_path = temp_registry_path("instances-prune");
+        std::env::set_var("UNITY_CLI_REGISTRY_PATH", &registry_path);
+
+        let (port, server) = spawn_bridge(|_| success(json!({}))).await;
+        let closed = TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .expect("listener should bind");
+        let closed_port = closed
+            .local_addr()
+            .expect("listener should have local addr")
+            .port();
+        drop(closed);
+        add_instance(&format!("127.0.0.1:{port}"), None).expect("add should succeed");
+        add_instance(&format!("127.0.0.1:{closed_port}"), Some("gone"))
+            .expect("add should succeed");
+
+        // `--stale` alone keeps a down entry with no known editor process.
+        let removed = prune_instances(false, true, 300)
+            .await
+            .expect("prune should succeed");
+        assert!(removed.is_empty());
+        let removed = prune_instances(true, false, 300)
+            .await
+            .expect("prune should succeed");
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].name.as_deref(), Some("gone"));
+        assert_eq!(registry_summary().expect("summary should load").entries, 1);
+
+        server.abort();
+        std::env::remove_var("UNITY_CLI_REGISTRY_PATH");
+        remove_registry_files(&registry_path);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    #[allow(clippy::await_holding_lock)]
+    async fn concurrent_registry_updates_keep_every_entry() {
+        let _guard = registry_lock().lock().expect("lock should succeed");
+        let registry_path = temp_registry_path("instances-stress");
+        std::env::set_var("UNITY_CLI_REGISTRY_PATH", &registry_path);
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for index in 0..16_u16 {
+            tasks.spawn(async move {
+                add_instance(
+                    &format!("127.0.0.1:{}", 1 + index),
+                    Some(&format!("editor{index}")),
+                )?;
+                list_instances("127.0.0.1", &[], &[], 100).await?;
+                registry_summary()
+            });
+        }
+        while let Some(result) = tasks.join_next().await {
+            result
+                .expect("task should not panic")
+                .expect("registry access should succeed");
+        }
+
+        let summary = registry_summary().expect("registry should stay readable");
+        assert_eq!(summary.entries, 16);
+        for index in 0..16 {
+            assert!(resolve_instance(&format!("editor{index}")).is_ok());
+        }
+        let parent = registry_path
+            .parent()
+            .expect("registry should have a parent");
+        let stem = registry_path
+            .file_name()
+            .expect("registry should have a file name")
+            .to_string_lossy()
+            .to_string();
+        let leftovers = std::fs::read_dir(parent)
+            .expect("temp dir should be readable")
+            .filter_map(Result::ok)
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| {
+                name.starts_with(&stem) && (name.contains(".tmp-") || name.contains(".corrupt-"))
+            })
+            .count();
+        assert_eq!(leftovers, 0);
+
+        std::env::remove_var("UNITY_CLI_REGISTRY_PATH");
+        remove_registry_files(&registry_path);
+    }
+
+    #[test]
+    fn corrupt_registry_is_backed_up_and_replaced() {
+        let _guard = registry_lock().lock().expect("lock should succeed");
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let registry_path = dir.path().join("instances.json");
+        std::env::set_var("UNITY_CLI_REGISTRY_PATH", &registry_path);
+        let corrupt = "{\"entries\": [{\"id\": \"local";
+        std::fs::write(&registry_path, corrupt).expect("registry should be written");
+        let backups = || {
+            std::fs::read_dir(dir.path())
+                .expect("temp dir should be readable")
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_name().to_string_lossy().contains(".corrupt-"))
+                .collect::<Vec<_>>()
+        };
+
+        // Readers see an empty registry and leave the file alone.
+        let summary = registry_summary().expect("corrupt registry should read as empty");
+        assert_eq!(summary.entries, 0);
+        assert!(resolve_instance("127.0.0.1:6401").is_ok());
+        assert!(backups().is_empty());
+        assert_eq!(
+            std::fs::read_to_string(&registry_path).expect("registry should be readable"),
+            corrupt
+        );
+
+        add_instance("127.0.0.1:6401", None).expect("registry should be writable again");
+        let backups = backups();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(backups[0].path()).expect("backup should be readable"),
+            corrupt
+        );
+        assert_eq!(registry_summary().expect("summary should load").entries, 1);
+
+        std::env::remove_var("UNITY_CLI_REGISTRY_PATH");
     }
 }
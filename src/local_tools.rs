@@ -67,6 +67,7 @@ pub fn maybe_execute_local_tool(tool_name: &str, params: &Value) -> Option<Resul
         "update_index" => Some(local_update_index(params)),
         "find_symbol" => Some(local_find_symbol(params)),
         "find_refs" => Some(local_find_refs(params)),
+        "search_tools" => Some(local_search_tools(params)),
         _ => None,
     }
 }
@@ -257,6 +258,7 @@ fn local_get_symbols(params: &Value) -> Result<Value> {
 
 fn local_build_index(params: &Value) -> Result<Value> {
     let root = project_root()?;
+    let params = &with_index_defaults(params);
     if let Some(result) = crate::lsp::maybe_execute("build_index", params, &root) {
         return result;
     }
@@ -651,14 +653,130 @@ fn local_find_refs(params: &Value) -> Result<Value> {
     Ok(response)
 }
 
-fn project_root() -> Result<PathBuf> {
+fn local_search_tools(params: &Value) -> Result<Value> {
+    let query = params
+        .get("query")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("search_tools requires `query`"))?;
+    let category = params.get("category").and_then(Value::as_str);
+    let limit = params
+        .get("limit")
+        .and_then(Value::as_u64)
+        .unwrap_or(10)
+        .clamp(1, 200) as usize;
+
+    let matches = crate::tool_catalog::search_tools(query, category);
+    let total = matches.len();
+    let results = matches
+        .into_iter()
+        .take(limit)
+        .map(|item| serde_json::to_value(&item).context("Failed to encode search_tools result"))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(json!({
+        "success": true,
+        "query": query,
+        "results": results,
+        "count": results.len(),
+        "total": total
+    }))
+}
+
+/// Fills `excludePackageCache`/`scope` from the `index.*` config keys when the
+/// caller did not pass them.
+fn with_index_defaults(params: &Value) -> Value {
+    let mut params = params.clone();
+    if let Some(map) = params.as_object_mut() {
+        for (param, key) in [
+            ("excludePackageCache", "index.exclude_package_cache"),
+            ("scope", "index.scope"),
+        ] {
+            if !map.contains_key(param) {
+                let value = crate::config::setting(key);
+                if !value.is_null() {
+                    map.insert(param.to_string(), value);
+                }
+            }
+        }
+    }
+    params
+}
+
+static PROJECT_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Pins the project root for this process (`--project <dir>`). `dir` may be
+/// the project root or any directory inside it.
+pub fn set_project_override(dir: &Path) -> Result<PathBuf> {
+    let start = dir
+        .canonicalize()
+        .with_context(|| format!("Project directory not found: {}", dir.display()))?;
+    let root = find_unity_project_root(&start).ok_or_else(|| {
+        anyhow!(
+            "No Unity project found at or above {} (expected Assets/ and ProjectSettings/ProjectVersion.txt)",
+            start.display()
+        )
+    })?;
+    PROJECT_OVERRIDE
+        .set(root.clone())
+        .map_err(|_| anyhow!("Project root was already set"))?;
+    Ok(root)
+}
+
+/// Resolves the Unity project root: `--project`, then `UNITY_PROJECT_ROOT`,
+/// then the nearest ancestor of the current directory that looks like a
+/// Unity project.
+pub fn project_root() -> Result<PathBuf> {
+    if let Some(root) = PROJECT_OVERRIDE.get() {
+        return Ok(root.clone());
+    }
     if let Ok(raw) = env::var("UNITY_PROJECT_ROOT") {
         let trimmed = raw.trim();
         if !trimmed.is_empty() {
             return Ok(PathBuf::from(trimmed));
         }
     }
-    env::current_dir().context("Failed to resolve current directory")
+    let cwd = env::current_dir().context("Failed to resolve current directory")?;
+    find_unity_project_root(&cwd).ok_or_else(|| {
+        anyhow!(
+            "No Unity project found in {} or its parents (expected Assets/ and ProjectSettings/ProjectVersion.txt). Run inside a Unity project, pass --project <dir>, or set UNITY_PROJECT_ROOT.",
+            cwd.display()
+        )
+    })
+}
+
+pub fn find_unity_project_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| {
+            dir.join("Assets").is_dir() && dir.join("ProjectSettings/ProjectVersion.txt").is_file()
+        })
+        .map(Path::to_path_buf)
+}
+
+/// Summarizes the local symbol index without rebuilding it.
+pub fn index_status(root: &Path) -> Value {
+    let path = to_rel_project_path(root, &index_file_path(root));
+    match load_index_if_exists(root) {
+        Ok(Some(index)) => json!({
+            "path": path,
+            "exists": true,
+            "ready": index_is_ready(&index),
+            "indexedFiles": index.files.len(),
+            "indexedSymbols": index.files.values().map(|file| file.symbols.len()).sum::<usize>(),
+            "generatedAtEpochMs": index.generated_at_epoch_ms
+        }),
+        Ok(None) => json!({
+            "path": path,
+            "exists": false,
+            "ready": false
+        }),
+        Err(error) => json!({
+            "path": path,
+            "exists": true,
+            "ready": false,
+            "error": format!("{error:#}")
+        }),
+    }
 }
 
 fn resolve_existing_project_path(root: &Path, rel: &str) -> Result<PathBuf> {
@@ -1161,7 +1279,7 @@ fn to_rel_project_path(root: &Path, path: &Path) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::maybe_execute_local_tool;
+    use super::{find_unity_project_root, maybe_execute_local_tool};
     use serde_json::json;
     use std::path::Path;
     use std::sync::{Mutex, OnceLock};
@@ -1178,6 +1296,22 @@ mod tests {
         std::fs::write(path, content).expect("file should be written");
     }
 
+    #[test]
+    fn find_unity_project_root_walks_up_from_nested_dirs() {
+        let tmp = tempfile::tempdir().expect("temp dir should be created");
+        let project = tmp.path().join("Game");
+        let scripts = project.join("Assets/Scripts/Player");
+        std::fs::create_dir_all(&scripts).expect("assets should be created");
+        assert_eq!(find_unity_project_root(&scripts), None);
+
+        write_file(
+            &project.join("ProjectSettings/ProjectVersion.txt"),
+            "m_EditorVersion: 2022.3.10f1\n",
+        );
+        assert_eq!(find_unity_project_root(&scripts), Some(project.clone()));
+        assert_eq!(find_unity_project_root(&project), Some(project));
+    }
+
     #[test]
     fn read_returns_file_slice() {
         let _guard = env_lock().lock().expect("lock should succeed");
@@ -1415,4 +1549,27 @@ mod tests {
 
         std::env::remove_var("UNITY_PROJECT_ROOT");
     }
+
+    #[test]
+    fn search_tools_runs_offline_with_scores() {
+        let value = maybe_execute_local_tool(
+            "search_tools",
+            &json!({"query":"set ui slider value","limit":3}),
+        )
+        .expect("tool should be handled")
+        .expect("search_tools should succeed");
+
+        let results = value["results"]
+            .as_array()
+            .expect("results should be array");
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["name"], "set_ui_element_value");
+        assert!(
+            results[0]["score"]
+                .as_f64()
+                .expect("score should be number")
+                > 0.0
+        );
+        assert!(results[0]["params"].is_array());
+    }
 }
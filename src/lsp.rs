@@ -20,6 +20,16 @@ enum LspMode {
     Required,
 }
 
+impl LspMode {
+    fn as_str(self) -> &'static str {
+        match self {
+            LspMode::Off => "off",
+            LspMode::Auto => "auto",
+            LspMode::Required => "required",
+        }
+    }
+}
+
 #[derive(Debug)]
 struct LspCommand {
     program: String,
@@ -52,6 +62,23 @@ pub fn maybe_execute(
     }
 }
 
+/// Describes the effective LSP configuration for diagnostics output.
+pub fn describe() -> Value {
+    let command = match resolve_lsp_command() {
+        Ok(command) => json!({
+            "program": command.program,
+            "args": command.args
+        }),
+        Err(error) => json!({ "error": error.to_string() }),
+    };
+
+    json!({
+        "mode": lsp_mode().as_str(),
+        "requestTimeoutMs": lsp_timeout().as_millis() as u64,
+        "command": command
+    })
+}
+
 fn execute(tool_name: &str, params: &Value, project_root: &Path) -> Result<Value> {
     let mut session = LspSession::start(project_root)?;
     let value = match tool_name {
@@ -529,12 +556,7 @@ fn now_epoch_ms() -> u64 {
 }
 
 fn lsp_mode() -> LspMode {
-    let raw = env::var("UNITY_CLI_LSP_MODE")
-        .ok()
-        .unwrap_or_else(|| "off".to_string())
-        .to_ascii_lowercase();
-
-    match raw.as_str() {
+    match crate::config::setting("lsp.mode").as_str().unwrap_or("off") {
         "required" => LspMode::Required,
         "auto" => LspMode::Auto,
         _ => LspMode::Off,
@@ -542,10 +564,8 @@ fn lsp_mode() -> LspMode {
 }
 
 fn lsp_timeout() -> Duration {
-    let timeout_ms = env::var("UNITY_CLI_LSP_REQUEST_TIMEOUT_MS")
-        .ok()
-        .and_then(|value| value.trim().parse::<u64>().ok())
-        .filter(|value| *value > 0)
+    let timeout_ms = crate::config::setting("lsp.request_timeout_ms")
+        .as_u64()
         .unwrap_or(DEFAULT_TIMEOUT_MS);
     Duration::from_millis(timeout_ms)
 }
@@ -571,7 +591,8 @@ fn detect_rid() -> &'static str {
 }
 
 fn resolve_lsp_command() -> Result<LspCommand> {
-    if let Ok(raw) = env::var("UNITY_CLI_LSP_COMMAND") {
+    // `lsp.command` comes from UNITY_CLI_LSP_COMMAND or the config file.
+    if let Some(raw) = crate::config::setting("lsp.command").as_str() {
         let parts = raw
             .split_whitespace()
             .map(|value| value.to_string())
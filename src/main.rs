@@ -1,26 +1,49 @@
+mod aliases;
 mod cli;
+mod composite_tools;
 mod config;
+mod console_tail;
+mod guard;
 mod instances;
 mod local_tools;
 mod lsp;
+mod output;
+mod params;
+mod play;
+mod profiler;
+mod query;
+mod screenshot;
+mod template;
+mod test_history;
+mod test_report;
+mod test_runner;
+#[cfg(test)]
+mod test_support;
 mod tool_catalog;
 mod transport;
+mod visual;
 
-use std::fs;
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
 use std::path::PathBuf;
+use std::time::Duration;
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use clap::Parser;
 use serde_json::{json, Value};
 use tracing_subscriber::EnvFilter;
 
+use crate::aliases::AliasSet;
 use crate::cli::{
-    Cli, Command, InstancesCommand, OutputFormat, RawArgs, SceneCommand, SystemCommand, ToolCommand,
+    Cli, Command, ConfigCommand, ConsoleCommand, InstancesCommand, PlayCommand, ProfilerCommand,
+    RawArgs, SceneCommand, ScreenshotView, SystemCommand, TestCommand, ToolCommand, VisualCommand,
 };
 use crate::config::RuntimeConfig;
-use crate::instances::{list_instances, set_active_instance};
-use crate::tool_catalog::{is_known_tool, TOOL_NAMES};
-use crate::transport::UnityClient;
+use crate::instances::{
+    add_instance, instance_history, list_instances, prune_instances, remove_instance,
+    rename_instance, set_active_instance,
+};
+use crate::tool_catalog::{is_known_tool, tool_names};
 
 #[tokio::main]
 async fn main() {
@@ -33,28 +56,47 @@ async fn main() {
 async fn run() -> Result<()> {
     let cli = Cli::parse();
     init_tracing(cli.verbose)?;
+    // `config set --profile` may create the profile, so it must not be required to exist yet.
+    let profile = match &cli.command {
+        Command::Config {
+            command: ConfigCommand::Set { .. },
+        } => None,
+        _ => cli.profile.as_deref(),
+    };
+    let start_dir = match &cli.project {
+        Some(dir) => local_tools::set_project_override(dir)?,
+        None => std::env::current_dir().context("Failed to resolve current directory")?,
+    };
+    config::init(profile, &start_dir)?;
 
     match &cli.command {
         Command::Raw(args) => {
             let value = execute_raw(&cli, args).await?;
-            print_value(&value, cli.output)?;
+            print_value(&value, &cli)?;
         }
         Command::Tool { command } => match command {
             ToolCommand::List => {
-                if matches!(cli.output, OutputFormat::Json) {
-                    print_value(&serde_json::to_value(TOOL_NAMES)?, cli.output)?;
+                let aliases = load_aliases()?;
+                if cli.wants_structured_output() {
+                    let mut names = tool_names().collect::<Vec<&str>>();
+                    names.extend(aliases.iter().map(|alias| alias.name.as_str()));
+                    print_value(&serde_json::to_value(names)?, &cli)?;
                 } else {
-                    for name in TOOL_NAMES {
+                    for name in tool_names() {
                         println!("{name}");
                     }
+                    for alias in aliases.iter() {
+                        println!("{}  (alias: {})", alias.name, alias.definition());
+                    }
                 }
             }
             ToolCommand::Call(args) => {
                 let value = execute_raw(&cli, args).await?;
-                print_value(&value, cli.output)?;
+                print_value(&value, &cli)?;
             }
             ToolCommand::External(args) => {
-                let raw = parse_external_tool_command(args)?;
+                let aliases = load_aliases()?;
+                let raw = parse_external_tool_command(args, &aliases)?;
                 if !is_known_tool(&raw.tool_name) {
                     return Err(anyhow!(
                         "Unknown tool `{}`. Use `unity-cli tool list` to see supported names.",
@@ -62,7 +104,7 @@ async fn run() -> Result<()> {
                     ));
                 }
                 let value = execute_raw(&cli, &raw).await?;
-                print_value(&value, cli.output)?;
+                print_value(&value, &cli)?;
             }
         },
         Command::System { command } => match command {
@@ -72,7 +114,7 @@ async fn run() -> Result<()> {
                     params.insert("message".to_string(), Value::String(msg.clone()));
                 }
                 let value = execute_tool(&cli, "ping", Value::Object(params)).await?;
-                print_value(&value, cli.output)?;
+                print_value(&value, &cli)?;
             }
         },
         Command::Scene { command } => match command {
@@ -94,44 +136,466 @@ async fn run() -> Result<()> {
                 }
 
                 let value = execute_tool(&cli, "create_scene", Value::Object(params)).await?;
-                print_value(&value, cli.output)?;
+                print_value(&value, &cli)?;
             }
         },
         Command::Instances { command } => match command {
             InstancesCommand::List {
                 ports,
+                scan,
                 host,
                 timeout_ms,
             } => {
                 let parsed_ports = parse_ports(ports)?;
-                let statuses = list_instances(host, &parsed_ports, *timeout_ms).await?;
-
-                if matches!(cli.output, OutputFormat::Json) {
-                    print_value(&serde_json::to_value(&statuses)?, cli.output)?;
+                let scan_ports = parse_port_range(scan)?;
+                let statuses =
+                    list_instances(host, &parsed_ports, &scan_ports, *timeout_ms).await?;
+
+                print_value_with_default_format(
+                    &serde_json::to_value(&statuses)?,
+                    &cli,
+                    INSTANCES_LIST_FORMAT,
+                )?;
+            }
+            InstancesCommand::SetActive { id, timeout_ms } => {
+                let result = set_active_instance(id, *timeout_ms).await?;
+                print_value_with_default_format(
+                    &serde_json::to_value(&result)?,
+                    &cli,
+                    SET_ACTIVE_FORMAT,
+                )?;
+            }
+            InstancesCommand::Add { id, name } => {
+                let added = add_instance(id, name.as_deref())?;
+                print_value_with_default_format(
+                    &serde_json::to_value(&added)?,
+                    &cli,
+                    INSTANCE_ADDED_FORMAT,
+                )?;
+            }
+            InstancesCommand::Remove { instance } => {
+                let removed = remove_instance(instance)?;
+                print_value_with_default_format(
+                    &serde_json::to_value(&removed)?,
+                    &cli,
+                    INSTANCE_REMOVED_FORMAT,
+                )?;
+            }
+            InstancesCommand::Rename { instance, name } => {
+                let renamed = rename_instance(instance, name)?;
+                print_value_with_default_format(
+                    &serde_json::to_value(&renamed)?,
+                    &cli,
+                    INSTANCE_RENAMED_FORMAT,
+                )?;
+            }
+            InstancesCommand::Prune {
+                down,
+                stale,
+                timeout_ms,
+            } => {
+                let removed = prune_instances(*down, *stale, *timeout_ms).await?;
+                if removed.is_empty() && !cli.wants_structured_output() {
+                    println!("no {} instances", if *down { "down" } else { "stale" });
+                } else {
+                    print_value_with_default_format(
+                        &serde_json::to_value(&removed)?,
+                        &cli,
+                        INSTANCE_REMOVED_FORMAT,
+                    )?;
+                }
+            }
+            InstancesCommand::History { limit } => {
+                let history = instance_history(*limit)?;
+                if history.is_empty() && !cli.wants_structured_output() {
+                    println!("no activations recorded");
                 } else {
-                    for status in statuses {
-                        let marker = if status.active { "*" } else { " " };
+                    print_value_with_default_format(
+                        &serde_json::to_value(&history)?,
+                        &cli,
+                        INSTANCE_HISTORY_FORMAT,
+                    )?;
+                }
+            }
+        },
+        Command::Config { command } => match command {
+            ConfigCommand::Show => {
+                print_value_with_default_format(
+                    &serde_json::to_value(config::effective_settings(&cli)?)?,
+                    &cli,
+                    CONFIG_VALUE_FORMAT,
+                )?;
+            }
+            ConfigCommand::Get { key } => {
+                print_value_with_default_format(
+                    &serde_json::to_value(config::resolve_setting(&cli, key)?)?,
+                    &cli,
+                    CONFIG_VALUE_FORMAT,
+                )?;
+            }
+            ConfigCommand::Set { key, value, user } => {
+                let result = config::set_setting(key, value, *user, cli.profile.as_deref())?;
+                print_value_with_default_format(
+                    &serde_json::to_value(&result)?,
+                    &cli,
+                    CONFIG_SET_FORMAT,
+                )?;
+            }
+        },
+        Command::Console { command } => match command {
+            ConsoleCommand::Tail(args) => {
+                let compile = |pattern: &Option<String>| {
+                    pattern
+                        .as_deref()
+                        .map(|pattern| {
+                            regex::Regex::new(pattern)
+                                .with_context(|| format!("Invalid regex `{pattern}`"))
+                        })
+                        .transpose()
+                };
+                let options = console_tail::TailOptions {
+                    follow: args.follow,
+                    types: console_tail::parse_types(&args.types)?,
+                    grep: compile(&args.grep)?,
+                    until: compile(&args.until_pattern)?,
+                    stack_traces: args.stack_traces,
+                    lines: args.lines,
+                    poll_interval: Duration::from_millis(args.interval_ms.max(50)),
+                    max_wait: args.max_wait_sec.map(Duration::from_secs),
+                };
+                let color =
+                    std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+                let structured = cli.wants_structured_output();
+                console_tail::tail(&RuntimeConfig::from_cli(&cli)?, &options, |entry| {
+                    if structured {
+                        print_value(entry, &cli)
+                    } else {
+                        println!("{}", console_tail::render_entry(entry, color));
+                        Ok(())
+                    }
+                })
+                .await?;
+            }
+        },
+        Command::Guard(args) => {
+            let config = RuntimeConfig::from_cli(&cli)?;
+            let mut allow = args
+                .allow
+                .iter()
+                .map(|pattern| {
+                    regex::Regex::new(pattern).with_context(|| format!("Invalid regex `{pattern}`"))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            if let Some(path) = &args.allowlist {
+                allow.extend(guard::load_allowlist(path)?);
+            }
+            let options = guard::GuardOptions {
+                fail_on: console_tail::parse_types(&args.fail_on)?,
+                allow,
+            };
+
+            let mut watch = guard::ConsoleWatch::start(&config).await?;
+            let exit_code = guard::run_wrapped(&config, &args.command, &mut watch).await?;
+            let entries = watch.into_entries();
+            let (offending, ignored) = options.classify(&entries);
+            if cli.wants_structured_output() {
+                print_value(
+                    &json!({ "exitCode": exit_code, "offending": offending, "ignored": ignored }),
+                    &cli,
+                )?;
+            } else {
+                let color =
+                    std::io::stderr().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+                for entry in &offending {
+                    eprintln!("{}", console_tail::render_entry(entry, color));
+                }
+            }
+            if !offending.is_empty() {
+                bail!(
+                    "{} console entr{} matched --fail-on {}",
+                    offending.len(),
+                    if offending.len() == 1 { "y" } else { "ies" },
+                    options.fail_on.join(",")
+                );
+            }
+            if exit_code != 0 {
+                bail!("Wrapped command exited with status {exit_code}");
+            }
+        }
+        Command::Play { command } => {
+            let config = RuntimeConfig::from_cli(&cli)?;
+            let poll_interval = Duration::from_millis(PLAY_POLL_INTERVAL_MS);
+            let transition_timeout = Duration::from_secs(PLAY_WAIT_TIMEOUT_SECS);
+            match command {
+                PlayCommand::Start { wait } => {
+                    let mut state = play::control(&config, "play_game").await?;
+                    if *wait {
+                        state = play::wait_for_play_state(
+                            &config,
+                            true,
+                            transition_timeout,
+                            poll_interval,
+                        )
+                        .await?;
+                    }
+                    print_value_with_default_format(&state, &cli, PLAY_STATE_FORMAT)?;
+                }
+                PlayCommand::Pause => {
+                    let state = play::control(&config, "pause_game").await?;
+                    print_value_with_default_format(&state, &cli, PLAY_STATE_FORMAT)?;
+                }
+                PlayCommand::Stop { wait } => {
+                    let mut state = play::control(&config, "stop_game").await?;
+                    if *wait {
+                        state = play::wait_for_play_state(
+                            &config,
+                            false,
+                            transition_timeout,
+                            poll_interval,
+                        )
+                        .await?;
+                    }
+                    print_value_with_default_format(&state, &cli, PLAY_STATE_FORMAT)?;
+                }
+                PlayCommand::Status => {
+                    let state = play::control(&config, "get_editor_state").await?;
+                    print_value_with_default_format(&state, &cli, PLAY_STATE_FORMAT)?;
+                }
+                PlayCommand::Session(args) => {
+                    let options = play::SessionOptions {
+                        duration: parse_duration(&args.duration, "--duration")?,
+                        screenshot_every: args
+                            .screenshot_every
+                            .as_deref()
+                            .map(|raw| parse_duration(raw, "--screenshot-every"))
+                            .transpose()?,
+                        collect_logs: args.collect_logs,
+                        out: match &args.out {
+                            Some(out) => out.clone(),
+                            None => PathBuf::from(format!(
+                                "play-session-{}",
+                                std::time::SystemTime::now()
+                                    .duration_since(std::time::UNIX_EPOCH)
+                                    .map(|elapsed| elapsed.as_secs())
+                                    .unwrap_or(0)
+                            )),
+                        },
+                        poll_interval,
+                    };
+                    let manifest = play::run_session(&config, &options).await?;
+                    if cli.wants_structured_output() {
+                        print_value(&manifest, &cli)?;
+                    } else {
+                        let count = |key: &str| manifest[key].as_array().map_or(0, Vec::len);
                         println!(
-                            "{} {:<21} {:<5} checked_at={}",
-                            marker, status.id, status.status, status.last_checked_at
+                            "session bundle: {} ({:.1}s, {} screenshot(s){}{})",
+                            options.out.display(),
+                            manifest["elapsedSeconds"].as_f64().unwrap_or(0.0),
+                            count("screenshots"),
+                            match manifest["logs"]["total"].as_u64() {
+                                Some(total) => format!(", {total} log entries"),
+                                None => String::new(),
+                            },
+                            if manifest["interrupted"] == true {
+                                ", interrupted"
+                            } else {
+                                ""
+                            },
                         );
+                        for error in manifest["errors"].as_array().into_iter().flatten() {
+                            eprintln!("Warning: {}", error.as_str().unwrap_or_default());
+                        }
                     }
                 }
             }
-            InstancesCommand::SetActive { id, timeout_ms } => {
-                let result = set_active_instance(id, *timeout_ms).await?;
-                let value = serde_json::to_value(&result)?;
-                if matches!(cli.output, OutputFormat::Json) {
-                    print_value(&value, cli.output)?;
+        }
+        Command::Screenshot(args) => {
+            let config = RuntimeConfig::from_cli(&cli)?;
+            let options = screenshot::CaptureOptions {
+                view: match args.view {
+                    ScreenshotView::Game => "game",
+                    ScreenshotView::Scene => "scene",
+                }
+                .to_string(),
+                width: args.width,
+                height: args.height,
+            };
+            match &args.open_dir {
+                Some(dir) => {
+                    let every = parse_duration(&args.every, "--every")?;
+                    for shot in 0..args.count {
+                        if shot > 0 {
+                            tokio::time::sleep(every).await;
+                        }
+                        let dest = screenshot::next_in_sequence(dir)?;
+                        let saved = screenshot::capture_to(&config, &options, &dest).await?;
+                        print_value_with_default_format(&saved, &cli, SCREENSHOT_FORMAT)?;
+                    }
+                }
+                None => {
+                    let dest = match &args.out {
+                        Some(out) => out.clone(),
+                        None => PathBuf::from(format!(
+                            "screenshot-{}-{}.png",
+                            options.view,
+                            std::time::SystemTime::now()
+                                .duration_since(std::time::UNIX_EPOCH)
+                                .map(|elapsed| elapsed.as_secs())
+                                .unwrap_or(0)
+                        )),
+                    };
+                    let saved = screenshot::capture_to(&config, &options, &dest).await?;
+                    print_value_with_default_format(&saved, &cli, SCREENSHOT_FORMAT)?;
+                }
+            }
+        }
+        Command::Visual { command } => match command {
+            VisualCommand::Compare(args) => {
+                if !(0.0..=1.0).contains(&args.pixel_threshold) {
+                    bail!("--pixel-threshold must be between 0 and 1");
+                }
+                let options = visual::CompareOptions {
+                    baseline: args.baseline.clone(),
+                    current: args.current.clone(),
+                    threshold: visual::parse_threshold(&args.threshold)?,
+                    pixel_threshold: args.pixel_threshold,
+                    mask: match &args.mask {
+                        Some(path) => visual::Mask::load(path)?,
+                        None => visual::Mask::default(),
+                    },
+                    out: args.out.clone(),
+                };
+                let report = visual::compare_dirs(&options)?;
+                if cli.wants_structured_output() {
+                    print_value(&report, &cli)?;
                 } else {
-                    println!(
-                        "active instance changed: {} -> {}",
-                        result.previous_id.as_deref().unwrap_or("(none)"),
-                        result.active_id
+                    for image in report["images"].as_array().into_iter().flatten() {
+                        let status = image["status"].as_str().unwrap_or("");
+                        let detail = match image["diffRatio"].as_f64() {
+                            Some(ratio) => format!(" {:.3}%", ratio * 100.0),
+                            None => match image["error"].as_str() {
+                                Some(error) => format!(" ({error})"),
+                                None => String::new(),
+                            },
+                        };
+                        println!(
+                            "{:<13} {}{detail}",
+                            status.to_uppercase(),
+                            image["name"].as_str().unwrap_or("")
+                        );
+                    }
+                    println!("report: {}", options.out.join("report.html").display());
+                }
+                if report["passed"] != true {
+                    bail!(
+                        "{} image(s) differ above --threshold {}",
+                        report["summary"]["failed"],
+                        args.threshold
                     );
                 }
             }
         },
+        Command::Profiler { command } => match command {
+            ProfilerCommand::Record(args) => {
+                let format = args
+                    .trace_format
+                    .or_else(|| args.out.as_deref().map(cli::TraceFormat::for_path))
+                    .unwrap_or(cli::TraceFormat::Chrome);
+                let options = profiler::RecordOptions {
+                    duration: parse_duration(&args.duration, "--duration")?,
+                    metrics: args.metrics.clone(),
+                    interval: Duration::from_millis(args.interval_ms.max(1)),
+                    deep: args.deep,
+                    save_data: args.save_data,
+                };
+                let out = args
+                    .out
+                    .clone()
+                    .unwrap_or_else(|| PathBuf::from(format!("profile.{}", format.extension())));
+                let recording = profiler::record(&RuntimeConfig::from_cli(&cli)?, &options).await?;
+                std::fs::write(&out, recording.render(format)?)
+                    .with_context(|| format!("Failed to write trace: {}", out.display()))?;
+                let summary = json!({
+                    "path": out.to_string_lossy(),
+                    "format": format.name(),
+                    "samples": recording.samples.len(),
+                    "metrics": recording.metrics.len(),
+                    "elapsedSeconds": (recording.elapsed.as_secs_f64() * 10.0).round() / 10.0,
+                    "interrupted": recording.interrupted,
+                    "outputPath": recording.session.get("outputPath").cloned().unwrap_or(Value::Null),
+                });
+                print_value_with_default_format(&summary, &cli, PROFILER_RECORD_FORMAT)?;
+            }
+        },
+        Command::Test { command } => match command {
+            TestCommand::Run(args) => {
+                let options = test_runner::TestRunOptions {
+                    mode: test_runner::parse_mode(&args.mode)?,
+                    filter: args.filter.clone(),
+                    category: args.category.clone(),
+                    namespace: args.namespace.clone(),
+                    max_wait: Duration::from_secs(args.max_wait_sec),
+                    poll_interval: Duration::from_millis(TEST_POLL_INTERVAL_MS),
+                    progress: true,
+                };
+                let status = test_runner::run_with_reruns(
+                    &RuntimeConfig::from_cli(&cli)?,
+                    &options,
+                    args.rerun_failed,
+                )
+                .await?;
+                if let Err(error) = test_history::record_run(&status) {
+                    eprintln!("Warning: failed to record test history: {error:#}");
+                }
+                if let Err(error) = test_history::save_last_run(&status) {
+                    eprintln!("Warning: failed to save the test run for `test report`: {error:#}");
+                }
+                if cli.wants_structured_output() {
+                    print_value(&status, &cli)?;
+                } else {
+                    println!("{}", test_runner::render_summary(&status));
+                }
+                let failed = test_runner::failed_count(&status);
+                if failed > 0 {
+                    bail!("{failed} test(s) failed");
+                }
+            }
+            TestCommand::Flaky { limit } => {
+                let tests = test_history::flaky_tests(Some(*limit))?;
+                if tests.is_empty() && !cli.wants_structured_output() {
+                    println!("no failing tests recorded");
+                } else {
+                    print_value_with_default_format(
+                        &serde_json::to_value(&tests)?,
+                        &cli,
+                        TEST_FLAKY_FORMAT,
+                    )?;
+                }
+            }
+            TestCommand::Report(args) => {
+                let report = match &args.input {
+                    Some(path) => test_report::TestReport::load(path)?,
+                    None => {
+                        let status = execute_tool(&cli, "get_test_status", json!({})).await?;
+                        if status.get("status").and_then(Value::as_str) != Some("completed") {
+                            bail!("No completed test run; run `unity-cli test run` first or pass --input");
+                        }
+                        // After `--rerun-failed` the editor only holds the last
+                        // rerun, so the saved aggregate of that run replaces it.
+                        match test_history::last_run_for(&status)? {
+                            Some(aggregate) => test_report::TestReport::from_json(&aggregate)?,
+                            None => test_report::TestReport::from_json(&status)?,
+                        }
+                    }
+                };
+                let rendered = report.render(args.report_format);
+                match &args.out {
+                    Some(path) => std::fs::write(path, rendered)
+                        .with_context(|| format!("Failed to write report: {}", path.display()))?,
+                    None => print!("{rendered}"),
+                }
+            }
+        },
     }
 
     Ok(())
@@ -148,13 +612,14 @@ async fn execute_tool(cli: &Cli, tool_name: &str, params: Value) -> Result<Value
     }
 
     let config = RuntimeConfig::from_cli(cli)?;
-    let mut client = UnityClient::connect(&config).await.with_context(|| {
-        format!(
-            "Failed to connect to Unity at {}:{}",
-            config.host, config.port
-        )
-    })?;
-    client.call_tool(tool_name, params).await
+    if let Some(composite_result) =
+        composite_tools::maybe_execute_composite_tool(&config, tool_name, &params).await
+    {
+        return composite_result;
+    }
+
+    let policy = play::resolve_policy(cli.play_mode_policy);
+    play::call_with_policy(&config, tool_name, params, policy).await
 }
 
 fn load_params(args: &RawArgs) -> Result<Value> {
@@ -162,29 +627,58 @@ fn load_params(args: &RawArgs) -> Result<Value> {
         return Err(anyhow!("Use either --json or --params-file, not both"));
     }
 
+    let mut params = args.base_params.clone().unwrap_or_else(|| json!({}));
+
     if let Some(file) = &args.params_file {
-        let content = fs::read_to_string(file)
-            .with_context(|| format!("Failed to read params file: {}", file.display()))?;
-        return parse_json_object(&content);
+        merge_json(&mut params, params::read_params_file(file)?);
+    } else if let Some(inline) = &args.json {
+        merge_json(&mut params, parse_json_object(inline)?);
     }
 
-    if let Some(inline) = &args.json {
-        return parse_json_object(inline);
+    for assignment in &args.params {
+        params::apply_param(&mut params, assignment)?;
     }
 
-    Ok(json!({}))
+    Ok(params)
 }
 
-fn parse_external_tool_command(args: &[String]) -> Result<RawArgs> {
+/// Recursively merges `overlay` into `base`; non-object values replace.
+fn merge_json(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base_map), Value::Object(overlay_map)) => {
+            for (key, value) in overlay_map {
+                match base_map.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        base_map.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+fn load_aliases() -> Result<AliasSet> {
+    AliasSet::from_definitions(&config::layers().aliases())
+}
+
+fn parse_external_tool_command(args: &[String], aliases: &AliasSet) -> Result<RawArgs> {
     if args.is_empty() {
         return Err(anyhow!(
             "Tool name is required. Use `unity-cli tool list` to see available tools."
         ));
     }
 
-    let tool_name = args[0].clone();
+    let alias = aliases.get(&args[0]);
+    let tool_name = alias
+        .map(|alias| alias.tool_name.clone())
+        .unwrap_or_else(|| args[0].clone());
     let mut json = None;
     let mut params_file = None;
+    let mut params = Vec::new();
+    let mut positional = Vec::new();
+    let mut named = BTreeMap::new();
 
     let mut idx = 1;
     while idx < args.len() {
@@ -205,21 +699,54 @@ fn parse_external_tool_command(args: &[String]) -> Result<RawArgs> {
             params_file = Some(PathBuf::from(value));
         } else if let Some(value) = arg.strip_prefix("--params-file=") {
             params_file = Some(PathBuf::from(value));
+        } else if arg == "-p" || arg == "--param" {
+            idx += 1;
+            let value = args
+                .get(idx)
+                .ok_or_else(|| anyhow!("`{arg}` requires a key=value pair"))?;
+            params.push(value.clone());
+        } else if let Some(value) = arg.strip_prefix("--param=") {
+            params.push(value.to_string());
+        } else if alias.is_some() && arg == "--var" {
+            idx += 1;
+            let value = args
+                .get(idx)
+                .ok_or_else(|| anyhow!("`--var` requires a name=value pair"))?;
+            insert_alias_var(&mut named, value)?;
+        } else if let (Some(_), Some(value)) = (alias, arg.strip_prefix("--var=")) {
+            insert_alias_var(&mut named, value)?;
+        } else if alias.is_some() && !arg.starts_with('-') {
+            positional.push(arg.clone());
         } else {
             return Err(anyhow!(
-                "Unsupported argument `{arg}` for `unity-cli tool <tool>`. Use --json or --params-file."
+                "Unsupported argument `{arg}` for `unity-cli tool <tool>`. Use --json, --params-file or --param."
             ));
         }
         idx += 1;
     }
 
+    let base_params = match alias {
+        Some(alias) => Some(alias.expand(&positional, &named)?),
+        None => None,
+    };
+
     Ok(RawArgs {
         tool_name,
         json,
         params_file,
+        params,
+        base_params,
     })
 }
 
+fn insert_alias_var(named: &mut BTreeMap<String, String>, raw: &str) -> Result<()> {
+    let (name, value) = raw
+        .split_once('=')
+        .ok_or_else(|| anyhow!("`--var` expects name=value, got `{raw}`"))?;
+    named.insert(name.trim().to_string(), value.to_string());
+    Ok(())
+}
+
 fn parse_json_object(raw: &str) -> Result<Value> {
     let value: Value = serde_json::from_str(raw).context("Failed to parse JSON parameters")?;
     if !value.is_object() {
@@ -250,20 +777,93 @@ fn parse_ports(raw: &Option<String>) -> Result<Vec<u16>> {
     Ok(ports)
 }
 
-fn print_value(value: &Value, format: OutputFormat) -> Result<()> {
-    match format {
-        OutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(value)?);
-        }
-        OutputFormat::Text => {
-            if let Some(text) = value.as_str() {
-                println!("{text}");
-            } else {
-                println!("{}", serde_json::to_string_pretty(value)?);
-            }
-        }
+/// Parses `500ms`, `30s`, `2m`, `1h` or a bare number of seconds.
+fn parse_duration(raw: &str, flag: &str) -> Result<Duration> {
+    let trimmed = raw.trim();
+    let split = trimmed
+        .find(|ch: char| !ch.is_ascii_digit() && ch != '.')
+        .unwrap_or(trimmed.len());
+    let (number, unit) = trimmed.split_at(split);
+    let value = number
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite() && *value > 0.0)
+        .with_context(|| format!("Invalid {flag} `{raw}`; use e.g. 500ms, 30s or 2m"))?;
+    let seconds = match unit {
+        "ms" => value / 1000.0,
+        "" | "s" => value,
+        "m" => value * 60.0,
+        "h" => value * 3600.0,
+        _ => bail!("Invalid {flag} `{raw}`; use e.g. 500ms, 30s or 2m"),
+    };
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+fn parse_port_range(raw: &Option<String>) -> Result<Vec<u16>> {
+    let Some(range) = raw else {
+        return Ok(Vec::new());
+    };
+
+    let parse = |token: &str| {
+        token
+            .trim()
+            .parse::<u16>()
+            .with_context(|| format!("Invalid port in --scan: {token}"))
+    };
+    let (start, end) = match range.split_once('-') {
+        Some((start, end)) => (parse(start)?, parse(end)?),
+        None => (parse(range)?, parse(range)?),
+    };
+    if start > end {
+        bail!("Invalid --scan range {range}: start must not exceed end");
     }
-    Ok(())
+    Ok((start..=end).collect())
+}
+
+const TEST_POLL_INTERVAL_MS: u64 = 1000;
+const PLAY_POLL_INTERVAL_MS: u64 = 500;
+const PLAY_WAIT_TIMEOUT_SECS: u64 = 60;
+
+const INSTANCES_LIST_FORMAT: &str =
+    "{{if .active}}*{{else}} {{end}} {{.id | pad 21}} {{.name | pad 10}} {{.status | pad 12}} {{if .latency_ms}}{{.latency_ms}}ms{{else}}-{{end}} bridge={{.bridge_version | default \"-\"}} {{.unity_version | default \"-\" | pad 12}} {{.project_path | default \"-\"}}{{if .stale}}  (stale: pid {{.pid}} exited){{end}}";
+const SET_ACTIVE_FORMAT: &str =
+    "active instance changed: {{.previous_id | default \"(none)\"}} -> {{.active_id}}";
+const INSTANCE_ADDED_FORMAT: &str = "added {{.id}}{{if .name}} as {{.name}}{{end}}";
+const INSTANCE_REMOVED_FORMAT: &str =
+    "removed {{.id}}{{if .name}} ({{.name}}){{end}}{{if .active}}; no instance is active now{{end}}";
+const INSTANCE_RENAMED_FORMAT: &str = "renamed {{.id}} to {{.name}}";
+const INSTANCE_HISTORY_FORMAT: &str =
+    "{{.at}} {{.previous_id | default \"(none)\"}} -> {{.active_id}}{{if .name}} ({{.name}}){{end}}";
+
+const PLAY_STATE_FORMAT: &str = "{{.mode}}{{if .message}} ({{.message}}){{end}}";
+const SCREENSHOT_FORMAT: &str =
+    "{{.path}} {{if .width}}{{.width}}x{{.height}}{{else}}(size unknown){{end}}";
+const PROFILER_RECORD_FORMAT: &str =
+    "wrote {{.path}} ({{.samples}} samples of {{.metrics}} metrics \
+     over {{.elapsedSeconds}}s{{if .interrupted}}, interrupted{{end}})\
+     {{if .outputPath}}\nprofiler data: {{.outputPath}}{{end}}";
+const TEST_FLAKY_FORMAT: &str =
+    "{{.flaky | pad 3}} flaky {{.failed | pad 3}} failed {{.runs | pad 4}} runs {{.mode | pad 9}} {{.name}}";
+
+const CONFIG_VALUE_FORMAT: &str = "{{.key}} = {{.value | default \"(unset)\"}}  # {{.source}}";
+const CONFIG_SET_FORMAT: &str = "{{.key}} = {{.value}} written to {{.path}}";
+
+fn print_value(value: &Value, cli: &Cli) -> Result<()> {
+    output::print_value(
+        value,
+        cli.output,
+        cli.query.as_deref(),
+        cli.format.as_deref(),
+    )
+}
+
+/// Like `print_value`, but plain text output uses `default_format` instead of
+/// pretty JSON.
+fn print_value_with_default_format(value: &Value, cli: &Cli, default_format: &str) -> Result<()> {
+    if cli.wants_structured_output() {
+        return print_value(value, cli);
+    }
+    output::print_value(value, cli.output, None, Some(default_format))
 }
 
 fn init_tracing(verbose: u8) -> Result<()> {
@@ -286,7 +886,12 @@ fn init_tracing(verbose: u8) -> Result<()> {
 
 #[cfg(test)]
 mod tests {
-    use super::{parse_external_tool_command, parse_json_object, parse_ports};
+    use super::{
+        load_params, parse_duration, parse_external_tool_command, parse_json_object,
+        parse_port_range, parse_ports, AliasSet,
+    };
+    use std::collections::BTreeMap;
+    use std::time::Duration;
 
     #[test]
     fn parse_ports_deduplicates_values() {
@@ -300,6 +905,38 @@ mod tests {
         assert!(format!("{err:#}").contains("Invalid port"));
     }
 
+    #[test]
+    fn parse_port_range_expands_inclusive_ranges() {
+        let parsed = parse_port_range(&Some("6400-6403".to_string())).expect("range should parse");
+        assert_eq!(parsed, vec![6400, 6401, 6402, 6403]);
+        let single = parse_port_range(&Some("6400".to_string())).expect("port should parse");
+        assert_eq!(single, vec![6400]);
+        assert!(parse_port_range(&Some("6420-6400".to_string())).is_err());
+        assert!(parse_port_range(&Some("64x0-6410".to_string())).is_err());
+    }
+
+    #[test]
+    fn parse_duration_accepts_units() {
+        assert_eq!(
+            parse_duration("500ms", "--d").expect("ms"),
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            parse_duration("30s", "--d").expect("s"),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            parse_duration("1.5m", "--d").expect("m"),
+            Duration::from_secs(90)
+        );
+        assert_eq!(
+            parse_duration("2", "--d").expect("bare"),
+            Duration::from_secs(2)
+        );
+        assert!(parse_duration("0s", "--d").is_err());
+        assert!(parse_duration("5 days", "--d").is_err());
+    }
+
     #[test]
     fn parse_json_object_accepts_object() {
         let value = parse_json_object("{\"foo\":\"bar\"}").expect("object should parse");
@@ -319,7 +956,8 @@ mod tests {
             "--json".to_string(),
             "{\"message\":\"hi\"}".to_string(),
         ];
-        let parsed = parse_external_tool_command(&args).expect("external args should parse");
+        let parsed = parse_external_tool_command(&args, &AliasSet::default())
+            .expect("external args should parse");
         assert_eq!(parsed.tool_name, "ping");
         assert_eq!(parsed.json.as_deref(), Some("{\"message\":\"hi\"}"));
         assert!(parsed.params_file.is_none());
@@ -328,8 +966,52 @@ mod tests {
     #[test]
     fn parse_external_tool_command_rejects_unknown_flag() {
         let args = vec!["ping".to_string(), "--unknown".to_string()];
-        let err =
-            parse_external_tool_command(&args).expect_err("unsupported option should be rejected");
+        let err = parse_external_tool_command(&args, &AliasSet::default())
+            .expect_err("unsupported option should be rejected");
         assert!(format!("{err:#}").contains("Unsupported argument"));
     }
+
+    #[test]
+    fn parse_external_tool_command_expands_alias_with_overrides() {
+        let mut definitions = BTreeMap::new();
+        definitions.insert(
+            "findgo".to_string(),
+            r#"find_gameobject {"name":"${1}","exactMatch":${exact:-false}}"#.to_string(),
+        );
+        let aliases = AliasSet::from_definitions(&definitions).expect("aliases should parse");
+        let args = vec![
+            "findgo".to_string(),
+            "Player".to_string(),
+            "--var".to_string(),
+            "exact=true".to_string(),
+            "--json".to_string(),
+            "{\"tag\":\"Enemy\"}".to_string(),
+        ];
+
+        let parsed = parse_external_tool_command(&args, &aliases).expect("alias should parse");
+        assert_eq!(parsed.tool_name, "find_gameobject");
+        let params = load_params(&parsed).expect("params should load");
+        assert_eq!(params["name"], "Player");
+        assert_eq!(params["exactMatch"], true);
+        assert_eq!(params["tag"], "Enemy");
+    }
+
+    #[test]
+    fn parse_external_tool_command_applies_params_over_json() {
+        let args = vec![
+            "find_gameobject".to_string(),
+            "--json".to_string(),
+            "{\"name\":\"Player\",\"filter\":{\"tag\":\"Enemy\"}}".to_string(),
+            "-p".to_string(),
+            "filter.layer=5".to_string(),
+            "--param=name=Boss".to_string(),
+        ];
+
+        let parsed = parse_external_tool_command(&args, &AliasSet::default())
+            .expect("external args should parse");
+        let params = load_params(&parsed).expect("params should load");
+        assert_eq!(params["name"], "Boss");
+        assert_eq!(params["filter"]["tag"], "Enemy");
+        assert_eq!(params["filter"]["layer"], 5);
+    }
 }
@@ -0,0 +1,192 @@
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::cli::OutputFormat;
+use crate::query::Query;
+use crate::template::Template;
+
+/// Applies the optional `--query` selector and prints the value either through
+/// a `--format` template or in the requested output format.
+pub fn print_value(
+    value: &Value,
+    format: OutputFormat,
+    query: Option<&str>,
+    template: Option<&str>,
+) -> Result<()> {
+    let selected = match query {
+        Some(expr) => Query::parse(expr)?.apply(value)?,
+        None => value.clone(),
+    };
+    if let Some(template) = template {
+        for line in Template::parse(template)?.render_lines(&selected)? {
+            println!("{line}");
+        }
+        return Ok(());
+    }
+
+    let rendered = render(&selected, format)?;
+    println!("{}", rendered.trim_end_matches('\n'));
+    Ok(())
+}
+
+pub fn render(value: &Value, format: OutputFormat) -> Result<String> {
+    Ok(match format {
+        OutputFormat::Json => serde_json::to_string_pretty(value)?,
+        OutputFormat::Text => match value {
+            Value::String(text) => text.clone(),
+            other => serde_json::to_string_pretty(other)?,
+        },
+        OutputFormat::Yaml => serde_yaml::to_string(value)?,
+        OutputFormat::Ndjson => match value {
+            Value::Array(items) => items
+                .iter()
+                .map(serde_json::to_string)
+                .collect::<serde_json::Result<Vec<_>>>()?
+                .join("\n"),
+            other => serde_json::to_string(other)?,
+        },
+        OutputFormat::Table => render_table(value),
+    })
+}
+
+/// Renders arrays of objects as aligned columns. A response object that wraps
+/// exactly one such array (e.g. `{ "success": true, "results": [...] }`) is
+/// unwrapped; other objects print as KEY/VALUE rows.
+fn render_table(value: &Value) -> String {
+    let rows = match value {
+        Value::Array(items) => items.as_slice(),
+        Value::Object(map) => {
+            let mut tables = map.values().filter(|value| is_object_array(value));
+            match (tables.next(), tables.next()) {
+                (Some(Value::Array(items)), None) => items.as_slice(),
+                _ => {
+                    let rows = map
+                        .iter()
+                        .map(|(key, value)| vec![key.clone(), cell(value)])
+                        .collect::<Vec<_>>();
+                    return format_rows(&["KEY".to_string(), "VALUE".to_string()], &rows);
+                }
+            }
+        }
+        scalar => return cell(scalar),
+    };
+
+    if rows.is_empty() {
+        return String::new();
+    }
+    if !rows.iter().all(Value::is_object) {
+        let rows = rows
+            .iter()
+            .map(|value| vec![cell(value)])
+            .collect::<Vec<_>>();
+        return format_rows(&["VALUE".to_string()], &rows);
+    }
+
+    let mut columns = Vec::<String>::new();
+    for row in rows.iter().filter_map(Value::as_object) {
+        for key in row.keys() {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+    }
+    let cells = rows
+        .iter()
+        .filter_map(Value::as_object)
+        .map(|row| {
+            columns
+                .iter()
+                .map(|column| row.get(column).map(cell).unwrap_or_default())
+                .collect()
+        })
+        .collect::<Vec<_>>();
+    format_rows(&columns, &cells)
+}
+
+fn is_object_array(value: &Value) -> bool {
+    matches!(value, Value::Array(items) if !items.is_empty() && items.iter().all(Value::is_object))
+}
+
+fn cell(value: &Value) -> String {
+    let text = match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    };
+    text.replace(['\n', '\r'], " ")
+}
+
+fn format_rows(header: &[String], rows: &[Vec<String>]) -> String {
+    let mut widths = header
+        .iter()
+        .map(|title| title.chars().count())
+        .collect::<Vec<_>>();
+    for row in rows {
+        for (idx, value) in row.iter().enumerate() {
+            widths[idx] = widths[idx].max(value.chars().count());
+        }
+    }
+
+    std::iter::once(header)
+        .chain(rows.iter().map(Vec::as_slice))
+        .map(|row| {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(value, width)| {
+                    let pad = width - value.chars().count();
+                    format!("{value}{}", " ".repeat(pad))
+                })
+                .collect::<Vec<_>>()
+                .join("  ");
+            line.trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render;
+    use crate::cli::OutputFormat;
+    use serde_json::json;
+
+    #[test]
+    fn table_unwraps_result_arrays_and_aligns_columns() {
+        let value = json!({
+            "success": true,
+            "results": [
+                { "name": "Player", "path": "/Player" },
+                { "name": "Enemy", "layer": 8 }
+            ]
+        });
+        let table = render(&value, OutputFormat::Table).expect("table should render");
+        assert_eq!(
+            table,
+            "name    path     layer\nPlayer  /Player\nEnemy            8"
+        );
+    }
+
+    #[test]
+    fn table_falls_back_to_key_value_rows() {
+        let value = json!({ "isPlaying": false, "version": "2.1.0" });
+        let table = render(&value, OutputFormat::Table).expect("table should render");
+        assert_eq!(
+            table,
+            "KEY        VALUE\nisPlaying  false\nversion    2.1.0"
+        );
+    }
+
+    #[test]
+    fn ndjson_and_yaml_render_arrays() {
+        let value = json!([{ "a": 1 }, { "a": 2 }]);
+        assert_eq!(
+            render(&value, OutputFormat::Ndjson).expect("ndjson should render"),
+            "{\"a\":1}\n{\"a\":2}"
+        );
+        assert_eq!(
+            render(&value, OutputFormat::Yaml).expect("yaml should render"),
+            "- a: 1\n- a: 2\n"
+        );
+    }
+}
@@ -0,0 +1,373 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{Map, Value};
+
+/// Formats accepted by `--params-file`, chosen from the file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamsFormat {
+    Json,
+    Json5,
+    Yaml,
+    Toml,
+}
+
+impl ParamsFormat {
+    pub fn from_path(path: &Path) -> Self {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("yaml" | "yml") => Self::Yaml,
+            Some("toml") => Self::Toml,
+            Some("json5") => Self::Json5,
+            _ => Self::Json,
+        }
+    }
+
+    /// Stdin has no extension: JSON objects are read as JSON, anything else as YAML.
+    fn sniff(content: &str) -> Self {
+        if content.trim_start().starts_with('{') {
+            Self::Json
+        } else {
+            Self::Yaml
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Json => "JSON",
+            Self::Json5 => "JSON5",
+            Self::Yaml => "YAML",
+            Self::Toml => "TOML",
+        }
+    }
+}
+
+/// Reads a params object from a file, or from stdin when `path` is `-`.
+pub fn read_params_file(path: &Path) -> Result<Value> {
+    let (content, format, source) = if path == Path::new("-") {
+        let mut content = String::new();
+        io::stdin()
+            .read_to_string(&mut content)
+            .context("Failed to read params from stdin")?;
+        let format = ParamsFormat::sniff(&content);
+        (content, format, "stdin".to_string())
+    } else {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read params file: {}", path.display()))?;
+        (
+            content,
+            ParamsFormat::from_path(path),
+            path.display().to_string(),
+        )
+    };
+
+    let value = parse_params(&content, format)
+        .with_context(|| format!("Failed to parse params from {source}"))?;
+    if !value.is_object() {
+        bail!("Params from {source} must be an object");
+    }
+    Ok(value)
+}
+
+pub fn parse_params(content: &str, format: ParamsFormat) -> Result<Value> {
+    let (message, location) = match format {
+        ParamsFormat::Json => match serde_json::from_str(content) {
+            Ok(value) => return Ok(value),
+            Err(err) => (err.to_string(), Some((err.line(), err.column()))),
+        },
+        ParamsFormat::Json5 => match json5::from_str(content) {
+            Ok(value) => return Ok(value),
+            Err(json5::Error::Message { msg, location }) => {
+                // pest renders a multi-line snippet; the final line holds the reason.
+                let reason = msg.lines().last().unwrap_or_default();
+                let reason = reason.trim().trim_start_matches("= ").to_string();
+                (reason, location.map(|loc| (loc.line, loc.column)))
+            }
+        },
+        ParamsFormat::Yaml => match serde_yaml::from_str(content) {
+            Ok(value) => return Ok(value),
+            Err(err) => {
+                let location = err.location().map(|loc| (loc.line(), loc.column()));
+                (err.to_string(), location)
+            }
+        },
+        ParamsFormat::Toml => match toml::from_str(content) {
+            Ok(value) => return Ok(value),
+            Err(err) => {
+                let location = err.span().map(|span| line_column(content, span.start));
+                (err.message().to_string(), location)
+            }
+        },
+    };
+
+    let message = strip_location_suffix(&message);
+    match location {
+        Some((line, column)) => bail!(
+            "{} error at line {line}, column {column}: {message}",
+            format.label()
+        ),
+        None => bail!("{} error: {message}", format.label()),
+    }
+}
+
+/// Converts a byte offset into a one-based line and column.
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .count()
+        + 1;
+    (line, column)
+}
+
+/// Parser messages often end with their own "at line N column M"; the
+/// location is reported separately so drop the duplicate.
+fn strip_location_suffix(message: &str) -> &str {
+    match message.find(" at line ") {
+        Some(idx) => message[..idx].trim_end_matches([',', ':']),
+        None => message,
+    }
+}
+
+/// Applies a `--param` assignment to a params object.
+///
+/// Supported forms:
+/// - `a.b.c=value`: value is coerced to bool/number/null/JSON when unambiguous
+/// - `key:=<json>`: value must be valid JSON
+/// - `key=@file`: value is the file contents as a string
+/// - `key:=@file`: value is the file contents parsed as JSON
+pub fn apply_param(target: &mut Value, raw: &str) -> Result<()> {
+    let eq = raw
+        .find('=')
+        .ok_or_else(|| anyhow!("Invalid --param `{raw}`. Expected key=value or key:=<json>"))?;
+    let (key, raw_json) = match raw[..eq].strip_suffix(':') {
+        Some(key) => (key, true),
+        None => (&raw[..eq], false),
+    };
+    let value_text = &raw[eq + 1..];
+
+    let value = match (value_text.strip_prefix('@'), raw_json) {
+        (Some(path), true) => {
+            let content = read_param_file(path)?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse JSON from {path} for `{key}`"))?
+        }
+        (Some(path), false) => Value::String(read_param_file(path)?),
+        (None, true) => serde_json::from_str(value_text)
+            .with_context(|| format!("Invalid JSON for `{key}`: {value_text}"))?,
+        (None, false) => coerce_value(value_text),
+    };
+
+    set_path(target, key, value)
+}
+
+fn read_param_file(path: &str) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("Failed to read param file: {path}"))
+}
+
+/// Interprets a plain `--param` value, falling back to a string whenever the
+/// text is not unambiguously another JSON type.
+fn coerce_value(text: &str) -> Value {
+    match text {
+        "true" => return Value::Bool(true),
+        "false" => return Value::Bool(false),
+        "null" => return Value::Null,
+        _ => {}
+    }
+
+    if looks_like_number(text) {
+        if let Ok(number) = serde_json::from_str::<Value>(text) {
+            return number;
+        }
+    }
+
+    if text.starts_with('{') || text.starts_with('[') || text.starts_with('"') {
+        if let Ok(value) = serde_json::from_str::<Value>(text) {
+            return value;
+        }
+    }
+
+    Value::String(text.to_string())
+}
+
+/// Accepts JSON number syntax except values with leading zeros such as `007`,
+/// which are usually identifiers rather than numbers.
+fn looks_like_number(text: &str) -> bool {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    if digits.is_empty() || !digits.starts_with(|c: char| c.is_ascii_digit()) {
+        return false;
+    }
+    !(digits.len() > 1 && digits.starts_with('0') && !digits[1..].starts_with('.'))
+}
+
+fn set_path(target: &mut Value, key: &str, value: Value) -> Result<()> {
+    let segments = key.split('.').collect::<Vec<_>>();
+    if segments.iter().any(|segment| segment.trim().is_empty()) {
+        bail!("Invalid --param key `{key}`");
+    }
+
+    let mut current = target;
+    for segment in &segments[..segments.len() - 1] {
+        let object = current
+            .as_object_mut()
+            .ok_or_else(|| anyhow!("Cannot set `{key}`: `{segment}` is not inside an object"))?;
+        current = object
+            .entry(segment.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !current.is_object() {
+            bail!("Cannot set `{key}`: `{segment}` already holds a non-object value");
+        }
+    }
+
+    let object = current
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("Cannot set `{key}` on a non-object value"))?;
+    object.insert(segments[segments.len() - 1].to_string(), value);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_param, parse_params, read_params_file, ParamsFormat};
+    use serde_json::{json, Value};
+    use std::path::Path;
+
+    fn applied(assignments: &[&str]) -> Value {
+        let mut params = json!({});
+        for assignment in assignments {
+            apply_param(&mut params, assignment).expect("param should apply");
+        }
+        params
+    }
+
+    #[test]
+    fn apply_param_coerces_unambiguous_values() {
+        let params = applied(&[
+            "flag=true",
+            "count=3",
+            "ratio=-0.5",
+            "nothing=null",
+            "code=007",
+            "name=Player",
+            "list=[1,2]",
+            "quoted=\"true\"",
+        ]);
+        assert_eq!(
+            params,
+            json!({
+                "flag": true,
+                "count": 3,
+                "ratio": -0.5,
+                "nothing": null,
+                "code": "007",
+                "name": "Player",
+                "list": [1, 2],
+                "quoted": "true"
+            })
+        );
+    }
+
+    #[test]
+    fn apply_param_builds_nested_paths_and_raw_json() {
+        let params = applied(&["a.b.c=1", "a.b.d:={\"x\":[true]}", "a.e=text"]);
+        assert_eq!(params["a"]["b"]["c"], 1);
+        assert_eq!(params["a"]["b"]["d"]["x"][0], true);
+        assert_eq!(params["a"]["e"], "text");
+    }
+
+    #[test]
+    fn apply_param_reads_file_values() {
+        let tmp = tempfile::tempdir().expect("temp dir should be created");
+        let text_path = tmp.path().join("body.txt");
+        let json_path = tmp.path().join("body.json");
+        std::fs::write(&text_path, "hello").expect("file should be written");
+        std::fs::write(&json_path, "{\"k\":2}").expect("file should be written");
+
+        let params = applied(&[
+            &format!("text=@{}", text_path.display()),
+            &format!("data:=@{}", json_path.display()),
+        ]);
+        assert_eq!(params["text"], "hello");
+        assert_eq!(params["data"]["k"], 2);
+    }
+
+    #[test]
+    fn apply_param_rejects_invalid_input() {
+        let mut params = json!({ "a": 1 });
+        assert!(apply_param(&mut params, "missing-separator").is_err());
+        assert!(apply_param(&mut params, "a.b=2").is_err());
+        assert!(apply_param(&mut params, "x:={not json").is_err());
+        assert!(apply_param(&mut params, "x..y=1").is_err());
+    }
+
+    #[test]
+    fn params_format_follows_extension() {
+        assert_eq!(
+            ParamsFormat::from_path(Path::new("a.yml")),
+            ParamsFormat::Yaml
+        );
+        assert_eq!(
+            ParamsFormat::from_path(Path::new("a.YAML")),
+            ParamsFormat::Yaml
+        );
+        assert_eq!(
+            ParamsFormat::from_path(Path::new("a.toml")),
+            ParamsFormat::Toml
+        );
+        assert_eq!(
+            ParamsFormat::from_path(Path::new("a.json5")),
+            ParamsFormat::Json5
+        );
+        assert_eq!(
+            ParamsFormat::from_path(Path::new("a.json")),
+            ParamsFormat::Json
+        );
+    }
+
+    #[test]
+    fn read_params_file_accepts_commented_formats() {
+        let tmp = tempfile::tempdir().expect("temp dir should be created");
+        let cases = [
+            ("p.yaml", "# comment\nname: Player\nposition: [0, 1, 0]\n"),
+            (
+                "p.toml",
+                "# comment\nname = \"Player\"\nposition = [0, 1, 0]\n",
+            ),
+            (
+                "p.json5",
+                "// comment\n{ name: 'Player', position: [0, 1, 0], }\n",
+            ),
+        ];
+        for (file, content) in cases {
+            let path = tmp.path().join(file);
+            std::fs::write(&path, content).expect("file should be written");
+            let params = read_params_file(&path).expect("params should parse");
+            assert_eq!(params, json!({ "name": "Player", "position": [0, 1, 0] }));
+        }
+    }
+
+    #[test]
+    fn parse_errors_report_line_and_column() {
+        let cases = [
+            (ParamsFormat::Json, "{\n  \"a\": 1,\n  oops\n}"),
+            (ParamsFormat::Json5, "{\n  a: 1,\n  b: ]\n}"),
+            (ParamsFormat::Yaml, "a: 1\nb: [1, 2\nc: 3\n"),
+            (ParamsFormat::Toml, "a = 1\nb = \n"),
+        ];
+        for (format, content) in cases {
+            let err = parse_params(content, format).expect_err("invalid input should fail");
+            let message = format!("{err:#}");
+            assert!(message.contains("at line "), "{format:?}: {message}");
+            assert!(message.contains(", column "), "{format:?}: {message}");
+        }
+    }
+}
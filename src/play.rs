@@ -0,0 +1,737 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use tokio::time::sleep;
+
+use crate::cli::PlayModePolicy;
+use crate::config::RuntimeConfig;
+use crate::transport::{normalize_response, UnityClient};
+use crate::{console_tail, guard, screenshot};
+
+/// How long to wait for the editor to enter or leave Play Mode.
+const PLAY_TRANSITION_TIMEOUT_SECS: u64 = 60;
+/// How long a session tolerates a lost connection (domain reloads) before
+/// giving up.
+const RECONNECT_GRACE_SECS: u64 = 60;
+
+const PLAY_MODE_BLOCKED: &str = "PLAY_MODE_BLOCKED";
+const BLOCKED_POLL_INTERVAL_MS: u64 = 500;
+
+/// `--play-mode-policy`, else the `play_mode_policy` setting.
+pub fn resolve_policy(flag: Option<PlayModePolicy>) -> PlayModePolicy {
+    flag.unwrap_or_else(
+        || match crate::config::setting("play_mode_policy").as_str() {
+            Some("stop") => PlayModePolicy::Stop,
+            Some("wait") => PlayModePolicy::Wait,
+            _ => PlayModePolicy::Fail,
+        },
+    )
+}
+
+/// Calls a tool; when the bridge rejects it with `PLAY_MODE_BLOCKED`, applies
+/// `policy` (stop or wait for Play Mode to end) and retries once. The block
+/// is recognised both as the host-level error envelope and as an `{error,
+/// code}` result from a handler that only refuses the command during play.
+pub async fn call_with_policy(
+    config: &RuntimeConfig,
+    tool_name: &str,
+    params: Value,
+    policy: PlayModePolicy,
+) -> Result<Value> {
+    let response = connect(config)
+        .await?
+        .call_tool_raw(tool_name, params.clone())
+        .await?;
+    if !is_play_mode_blocked(&response) {
+        return tool_result(tool_name, response);
+    }
+
+    let poll_interval = Duration::from_millis(BLOCKED_POLL_INTERVAL_MS);
+    match policy {
+        PlayModePolicy::Fail => {
+            return tool_result(tool_name, response)
+                .context("Pass --play-mode-policy stop or wait to leave Play Mode and retry");
+        }
+        PlayModePolicy::Stop => {
+            eprintln!(
+                "Warning: {tool_name} is blocked during Play Mode; stopping Play Mode and retrying (--play-mode-policy stop)"
+            );
+            call(config, "stop_game", json!({})).await?;
+            wait_for_play_state(
+                config,
+                false,
+                Duration::from_secs(PLAY_TRANSITION_TIMEOUT_SECS),
+                poll_interval,
+            )
+            .await?;
+        }
+        PlayModePolicy::Wait => {
+            eprintln!(
+                "Warning: {tool_name} is blocked during Play Mode; waiting for Play Mode to end (--play-mode-policy wait)"
+            );
+            tokio::select! {
+                waited = wait_for_play_state(config, false, Duration::MAX, poll_interval) => {
+                    waited?;
+                }
+                _ = tokio::signal::ctrl_c() => bail!("Interrupted while waiting for Play Mode to end"),
+            }
+        }
+    }
+
+    let response = connect(config)
+        .await?
+        .call_tool_raw(tool_name, params)
+        .await?;
+    tool_result(tool_name, response)
+}
+
+fn is_play_mode_blocked(response: &Value) -> bool {
+    [response.get("code"), response.pointer("/result/code")]
+        .into_iter()
+        .flatten()
+        .any(|code| code.as_str() == Some(PLAY_MODE_BLOCKED))
+}
+
+/// Unwraps the envelope and fails on an `error` the handler reported inside a
+/// successful response.
+fn tool_result(tool_name: &str, response: Value) -> Result<Value> {
+    let result = normalize_response(response)?;
+    if let Some(error) = result.get("error").and_then(Value::as_str) {
+        match result.get("code").and_then(Value::as_str) {
+            Some(code) => bail!("{tool_name} failed ({code}): {error}"),
+            None => bail!("{tool_name} failed: {error}"),
+        }
+    }
+    Ok(result)
+}
+
+/// Calls a Play Mode tool (`play_game`, `pause_game`, `stop_game`,
+/// `get_editor_state`) and flattens the result into `mode`/`message`/flags.
+pub async fn control(config: &RuntimeConfig, tool_name: &str) -> Result<Value> {
+    let result = call(config, tool_name, json!({})).await?;
+    Ok(summarize(&result))
+}
+
+/// Polls `get_editor_state` until `isPlaying` equals `playing`, tolerating the
+/// reconnects caused by the domain reload on entering Play Mode.
+pub async fn wait_for_play_state(
+    config: &RuntimeConfig,
+    playing: bool,
+    timeout: Duration,
+    poll_interval: Duration,
+) -> Result<Value> {
+    let started_at = Instant::now();
+    loop {
+        if let Ok(result) = call(config, "get_editor_state", json!({})).await {
+            let state = summarize(&result);
+            if state["isPlaying"].as_bool() == Some(playing) {
+                return Ok(state);
+            }
+            if playing && state["reason"] == "compile_errors" {
+                bail!("Cannot enter Play Mode because of compile errors");
+            }
+        }
+        if started_at.elapsed() > timeout {
+            bail!(
+                "Editor did not {} Play Mode within {}s",
+                if playing { "enter" } else { "leave" },
+                timeout.as_secs()
+            );
+        }
+        sleep(poll_interval).await;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SessionOptions {
+    pub duration: Duration,
+    pub screenshot_every: Option<Duration>,
+    pub collect_logs: bool,
+    pub out: PathBuf,
+    pub poll_interval: Duration,
+}
+
+/// Bundle contents collected while a session runs.
+struct Session {
+    started_at: Instant,
+    transitions: Vec<Value>,
+    screenshots: Vec<String>,
+    errors: Vec<String>,
+    last_mode: Option<String>,
+    interrupted: bool,
+    /// Console entries logged during the session, when collecting logs.
+    console: Option<guard::ConsoleWatch>,
+}
+
+impl Session {
+    fn record(&mut self, event: &str, state: Option<&Value>) {
+        let mut transition = json!({
+            "at": (self.started_at.elapsed().as_secs_f64() * 10.0).round() / 10.0,
+            "event": event,
+        });
+        if let Some(state) = state {
+            transition["mode"] = state["mode"].clone();
+            self.last_mode = state["mode"].as_str().map(str::to_string);
+        }
+        self.transitions.push(transition);
+    }
+}
+
+/// Enters Play Mode, keeps it running for `duration` while taking screenshots,
+/// then always stops it and writes `session.json` (plus `screenshots/` and
+/// `console.log`) to the bundle directory.
+pub async fn run_session(config: &RuntimeConfig, options: &SessionOptions) -> Result<Value> {
+    fs::create_dir_all(&options.out)
+        .with_context(|| format!("Failed to create session bundle: {}", options.out.display()))?;
+    let mut session = Session {
+        started_at: Instant::now(),
+        transitions: Vec::new(),
+        screenshots: Vec::new(),
+        errors: Vec::new(),
+        last_mode: None,
+        interrupted: false,
+        console: None,
+    };
+    if options.collect_logs {
+        session.console = Some(guard::ConsoleWatch::start(config).await?);
+    }
+
+    // One listener for the whole run, so Ctrl-C at any step still reaches
+    // the stop below instead of killing the process or being swallowed.
+    let driven = tokio::select! {
+        driven = drive(config, options, &mut session) => Some(driven),
+        _ = tokio::signal::ctrl_c() => None,
+    };
+    let driven = driven.unwrap_or_else(|| {
+        session.interrupted = true;
+        session.record("interrupted", None);
+        Ok(())
+    });
+
+    // Stop no matter how the session ended; retry on a fresh connection.
+    let stopped = match call(config, "stop_game", json!({})).await {
+        Ok(result) => Ok(result),
+        Err(_) => call(config, "stop_game", json!({})).await,
+    };
+    let stopped = match stopped {
+        Ok(_) => {
+            session.record("stop requested", None);
+            wait_for_play_state(
+                config,
+                false,
+                Duration::from_secs(PLAY_TRANSITION_TIMEOUT_SECS),
+                options.poll_interval,
+            )
+            .await
+            .map(|state| session.record("stopped", Some(&state)))
+        }
+        Err(error) => Err(error),
+    };
+
+    let mut logs = Value::Null;
+    if let Some(mut watch) = session.console.take() {
+        if let Err(error) = watch.poll(config).await {
+            session.errors.push(format!("collecting logs: {error:#}"));
+        }
+        logs = write_logs(options, &watch.into_entries())?;
+    }
+
+    let mut manifest = json!({
+        "bundle": options.out.to_string_lossy(),
+        "startedAt": unix_timestamp(),
+        "requestedDurationSeconds": options.duration.as_secs_f64(),
+        "elapsedSeconds": session.started_at.elapsed().as_secs_f64(),
+        "interrupted": session.interrupted,
+        "stoppedCleanly": stopped.is_ok(),
+        "transitions": session.transitions,
+        "screenshots": session.screenshots,
+        "logs": logs,
+        "errors": session.errors,
+    });
+    if let Err(error) = &driven {
+        manifest["error"] = json!(format!("{error:#}"));
+    }
+    let manifest_path = options.out.join("session.json");
+    fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)
+        .with_context(|| format!("Failed to write {}", manifest_path.display()))?;
+
+    driven?;
+    stopped.context("Failed to stop Play Mode")?;
+    Ok(manifest)
+}
+
+async fn drive(
+    config: &RuntimeConfig,
+    options: &SessionOptions,
+    session: &mut Session,
+) -> Result<()> {
+    let started = call(config, "play_game", json!({})).await?;
+    session.record("play requested", Some(&summarize(&started)));
+    let state = wait_for_play_state(
+        config,
+        true,
+        Duration::from_secs(PLAY_TRANSITION_TIMEOUT_SECS),
+        options.poll_interval,
+    )
+    .await?;
+    session.record("playing", Some(&state));
+    poll_console(config, session).await;
+
+    let playing_since = Instant::now();
+    let mut next_screenshot = options.screenshot_every.map(|_| Instant::now());
+    let mut disconnected_since: Option<Instant> = None;
+    while playing_since.elapsed() < options.duration {
+        if let (Some(due), Some(every)) = (next_screenshot, options.screenshot_every) {
+            if Instant::now() >= due {
+                capture(config, options, session).await;
+                next_screenshot = Some(due + every);
+            }
+        }
+
+        match call(config, "get_editor_state", json!({})).await {
+            Ok(result) => {
+                disconnected_since = None;
+                poll_console(config, session).await;
+                let state = summarize(&result);
+                if session.last_mode.as_deref() != state["mode"].as_str() {
+                    let event = match state["mode"].as_str() {
+                        Some("stopped") => "left play mode",
+                        Some(mode) => mode,
+                        None => "state changed",
+                    };
+                    session.record(event, Some(&state));
+                }
+                if state["mode"] == "stopped" {
+                    bail!(
+                        "Play Mode ended after {:.1}s of {:.1}s",
+                        playing_since.elapsed().as_secs_f64(),
+                        options.duration.as_secs_f64()
+                    );
+                }
+            }
+            Err(error) => {
+                let since = *disconnected_since.get_or_insert_with(Instant::now);
+                if since.elapsed() > Duration::from_secs(RECONNECT_GRACE_SECS) {
+                    return Err(error.context("Lost the connection to Unity during the session"));
+                }
+            }
+        }
+
+        let remaining = options.duration.saturating_sub(playing_since.elapsed());
+        sleep(options.poll_interval.min(remaining)).await;
+    }
+    Ok(())
+}
+
+/// Picks up console entries as the session runs, so a clear on entering Play
+/// Mode or during it does not lose them. Errors (domain reloads) are left to
+/// the final poll after stopping.
+async fn poll_console(config: &RuntimeConfig, session: &mut Session) {
+    if let Some(watch) = session.console.as_mut() {
+        let _ = watch.poll(config).await;
+    }
+}
+
+/// Saves a Game view screenshot into `screenshots/`; failures are recorded in
+/// the manifest instead of ending the session.
+async fn capture(config: &RuntimeConfig, options: &SessionOptions, session: &mut Session) {
+    let index = session.screenshots.len() + 1;
+    let elapsed = session.started_at.elapsed().as_secs();
+    let relative = format!("screenshots/{index:03}-{elapsed}s.png");
+    let saved = async {
+        let result = call(
+            config,
+            "capture_screenshot",
+            json!({ "captureMode": "game", "encodeAsBase64": true }),
+        )
+        .await?;
+        screenshot::save(&result, &options.out.join(&relative))?;
+        anyhow::Ok(())
+    }
+    .await;
+    match saved {
+        Ok(()) => session.screenshots.push(relative),
+        Err(error) => session
+            .errors
+            .push(format!("screenshot {index}: {error:#}")),
+    }
+}
+
+/// Writes `console.log` and returns the per-type counts for the manifest.
+fn write_logs(options: &SessionOptions, entries: &[Value]) -> Result<Value> {
+    let path = options.out.join("console.log");
+    let text = entries
+        .iter()
+        .map(|entry| console_tail::render_entry(entry, false) + "\n")
+        .collect::<String>();
+    fs::write(&path, text).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let mut counts = serde_json::Map::new();
+    for entry in entries {
+        let log_type = entry
+            .get("logType")
+            .and_then(Value::as_str)
+            .unwrap_or("Log");
+        let count = counts.entry(log_type).or_insert(json!(0));
+        *count = json!(count.as_u64().unwrap_or(0) + 1);
+    }
+    Ok(json!({ "file": "console.log", "total": entries.len(), "counts": counts }))
+}
+
+/// Flattens a Play Mode tool result into `mode` (`playing`, `paused` or
+/// `stopped`), the editor flags and the bridge's message.
+fn summarize(result: &Value) -> Value {
+    let state = result.get("state").unwrap_or(result);
+    let flag = |key: &str| state.get(key).and_then(Value::as_bool).unwrap_or(false);
+    let mode = match (flag("isPlaying"), flag("isPaused")) {
+        (true, true) => "paused",
+        (true, false) => "playing",
+        _ => "stopped",
+    };
+    let mut summary = json!({
+        "mode": mode,
+        "isPlaying": flag("isPlaying"),
+        "isPaused": flag("isPaused"),
+        "isCompiling": flag("isCompiling"),
+    });
+    if let Some(reason) = state.pointer("/playability/reason") {
+        summary["reason"] = reason.clone();
+    }
+    if let Some(message) = result.get("message") {
+        summary["message"] = message.clone();
+    }
+    summary
+}
+
+/// Calls a tool on a fresh connection and treats `status: "error"` or an
+/// `error` field in the result as a failure.
+async fn call(config: &RuntimeConfig, tool_name: &str, params: Value) -> Result<Value> {
+    let result = connect(config).await?.call_tool(tool_name, params).await?;
+    let failed = result.get("status").and_then(Value::as_str) == Some("error");
+    if let Some(error) = result.get("error").and_then(Value::as_str) {
+        bail!("{tool_name} failed: {error}");
+    }
+    if failed {
+        bail!("{tool_name} failed");
+    }
+    Ok(result)
+}
+
+async fn connect(config: &RuntimeConfig) -> Result<UnityClient> {
+    UnityClient::connect(config).await.with_context(|| {
+        format!(
+            "Failed to connect to Unity at {}:{}",
+            config.host, config.port
+        )
+    })
+}
+
+fn unix_timestamp() -> String {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs().to_string(),
+        Err(_) => "0".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{call_with_policy, control, run_session, SessionOptions};
+    use crate::cli::PlayModePolicy;
+    use crate::test_support::{config, spawn_bridge, spawn_editor, success};
+    use serde_json::{json, Value};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    /// Replies carrying a `code` are bridge-level error envelopes.
+    fn reply(result: Value) -> Value {
+        if result.get("code").is_some() {
+            result
+        } else {
+            success(result)
+        }
+    }
+
+    fn state(playing: bool) -> Value {
+        json!({ "status": "success", "state": { "isPlaying": playing, "isPaused": false } })
+    }
+
+    #[tokio::test]
+    async fn session_collects_bundle_and_stops_play_mode() {
+        static PLAYING: AtomicBool = AtomicBool::new(false);
+        static STOPS: AtomicUsize = AtomicUsize::new(0);
+        let (port, server) = spawn_bridge(|request| {
+            reply(match request["type"].as_str() {
+                Some("play_game") => {
+                    PLAYING.store(true, Ordering::SeqCst);
+                    json!({ "status": "success", "message": "Play mode scheduled in 300ms" })
+                }
+                Some("stop_game") => {
+                    STOPS.fetch_add(1, Ordering::SeqCst);
+                    PLAYING.store(false, Ordering::SeqCst);
+                    state(false)
+                }
+                Some("get_editor_state") => state(PLAYING.load(Ordering::SeqCst)),
+                // "PNG" in base64.
+                Some("capture_screenshot") => json!({ "path": "/tmp/x.png", "base64Data": "UE5H" }),
+                // "Clear on Play": entering Play Mode replaces the console
+                // with as many entries as it held before, and one more.
+                Some("read_console") => {
+                    let logs = if PLAYING.load(Ordering::SeqCst) || STOPS.load(Ordering::SeqCst) > 0
+                    {
+                        vec![
+                            json!({ "logType": "Log", "message": "spawned" }),
+                            json!({ "logType": "Error", "message": "boom" }),
+                        ]
+                    } else {
+                        vec![json!({ "logType": "Log", "message": "editor ready" })]
+                    };
+                    let count = request["params"]["count"].as_u64().unwrap_or(0) as usize;
+                    json!({
+                        "success": true,
+                        "totalCaptured": logs.len(),
+                        "logs": logs[logs.len().saturating_sub(count)..],
+                    })
+                }
+                _ => json!({}),
+            })
+        })
+        .await;
+
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let options = SessionOptions {
+            duration: Duration::from_millis(250),
+            screenshot_every: Some(Duration::from_millis(100)),
+            collect_logs: true,
+            out: dir.path().join("bundle"),
+            poll_interval: Duration::from_millis(20),
+        };
+        let manifest = run_session(&config(port), &options)
+            .await
+            .expect("session should succeed");
+
+        assert_eq!(STOPS.load(Ordering::SeqCst), 1);
+        assert_eq!(manifest["stoppedCleanly"], true);
+        let events = manifest["transitions"]
+            .as_array()
+            .expect("transitions")
+            .iter()
+            .map(|transition| transition["event"].as_str().unwrap_or("").to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec!["play requested", "playing", "stop requested", "stopped"]
+        );
+
+        let screenshots = manifest["screenshots"].as_array().expect("screenshots");
+        assert!(screenshots.len() >= 2, "{manifest}");
+        let first = screenshots[0].as_str().expect("screenshot path");
+        assert_eq!(
+            std::fs::read(options.out.join(first)).expect("screenshot should exist"),
+            b"PNG"
+        );
+        assert_eq!(manifest["logs"]["counts"]["Error"], 1);
+        let log = std::fs::read_to_string(options.out.join("console.log")).expect("console.log");
+        assert_eq!(log, "[Log] spawned\n[Error] boom\n");
+        assert!(options.out.join("session.json").is_file());
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn session_stops_play_mode_when_play_fails() {
+        static STOPS: AtomicUsize = AtomicUsize::new(0);
+        let (port, server) = spawn_bridge(|request| {
+            reply(match request["type"].as_str() {
+                Some("play_game") => json!({
+                    "status": "error",
+                    "error": "Cannot enter play mode due to compile errors"
+                }),
+                Some("stop_game") => {
+                    STOPS.fetch_add(1, Ordering::SeqCst);
+                    state(false)
+                }
+                Some("get_editor_state") => state(false),
+                _ => json!({}),
+            })
+        })
+        .await;
+
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let options = SessionOptions {
+            duration: Duration::from_secs(5),
+            screenshot_every: None,
+            collect_logs: false,
+            out: dir.path().join("bundle"),
+            poll_interval: Duration::from_millis(20),
+        };
+        let error = run_session(&config(port), &options)
+            .await
+            .expect_err("play failure should fail the session");
+        assert!(format!("{error:#}").contains("compile errors"));
+        assert_eq!(STOPS.load(Ordering::SeqCst), 1);
+        let manifest: Value = serde_json::from_str(
+            &std::fs::read_to_string(options.out.join("session.json")).expect("manifest"),
+        )
+        .expect("manifest should be JSON");
+        assert!(manifest["error"]
+            .as_str()
+            .is_some_and(|error| error.contains("play_game")));
+
+        let status = control(&config(port), "get_editor_state")
+            .await
+            .expect("status should succeed");
+        assert_eq!(status["mode"], "stopped");
+
+        server.abort();
+    }
+
+    fn blocked() -> Value {
+        json!({
+            "status": "error",
+            "error": "Command 'create_gameobject' is blocked during Play Mode",
+            "code": "PLAY_MODE_BLOCKED"
+        })
+    }
+
+    #[tokio::test]
+    async fn blocked_commands_follow_the_play_mode_policy() {
+        static PLAYING: AtomicBool = AtomicBool::new(true);
+        static STOPS: AtomicUsize = AtomicUsize::new(0);
+        let (port, server) = spawn_bridge(|request| {
+            reply(match request["type"].as_str() {
+                Some("create_gameobject") if PLAYING.load(Ordering::SeqCst) => blocked(),
+                Some("create_gameobject") => json!({ "name": "Player" }),
+                Some("stop_game") => {
+                    STOPS.fetch_add(1, Ordering::SeqCst);
+                    PLAYING.store(false, Ordering::SeqCst);
+                    state(false)
+                }
+                Some("get_editor_state") => state(PLAYING.load(Ordering::SeqCst)),
+                _ => json!({}),
+            })
+        })
+        .await;
+        let params = json!({ "name": "Player" });
+
+        let error = call_with_policy(
+            &config(port),
+            "create_gameobject",
+            params.clone(),
+            PlayModePolicy::Fail,
+        )
+        .await
+        .expect_err("fail policy should report the block");
+        let message = format!("{error:#}");
+        assert!(message.contains("PLAY_MODE_BLOCKED"));
+        assert!(message.contains("--play-mode-policy"));
+        assert_eq!(STOPS.load(Ordering::SeqCst), 0);
+
+        let created = call_with_policy(
+            &config(port),
+            "create_gameobject",
+            params,
+            PlayModePolicy::Stop,
+        )
+        .await
+        .expect("stop policy should retry after stopping");
+        assert_eq!(created["name"], "Player");
+        assert_eq!(STOPS.load(Ordering::SeqCst), 1);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn wait_policy_retries_once_play_mode_ends() {
+        static POLLS: AtomicUsize = AtomicUsize::new(0);
+        let (port, server) = spawn_bridge(|request| {
+            reply({
+                let playing = POLLS.load(Ordering::SeqCst) < 3;
+                match request["type"].as_str() {
+                    Some("save_scene") if playing => blocked(),
+                    Some("save_scene") => json!({ "saved": true }),
+                    Some("get_editor_state") => {
+                        POLLS.fetch_add(1, Ordering::SeqCst);
+                        state(playing)
+                    }
+                    Some("stop_game") => panic!("wait policy must not stop the game"),
+                    _ => json!({}),
+                }
+            })
+        })
+        .await;
+
+        let saved = call_with_policy(&config(port), "save_scene", json!({}), PlayModePolicy::Wait)
+            .await
+            .expect("wait policy should retry after play mode ends");
+        assert_eq!(saved["saved"], true);
+        assert!(POLLS.load(Ordering::SeqCst) >= 3);
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn handler_level_blocks_and_errors_inside_results_are_detected() {
+        static PLAYING: AtomicBool = AtomicBool::new(true);
+        static STOPS: AtomicUsize = AtomicUsize::new(0);
+        // ComponentHandler refuses edits during play inside a successful envelope.
+        let (port, server) = spawn_editor(|request| match request["type"].as_str() {
+            Some("set_component_field") if PLAYING.load(Ordering::SeqCst) => json!({
+                "error": "Component edits are blocked during Play Mode; pass runtime:true",
+                "code": "PLAY_MODE_BLOCKED"
+            }),
+            Some("set_component_field") => json!({ "updated": true }),
+            Some("get_component_values") => json!({
+                "error": "Component 'Rigidbody' not found",
+                "code": "COMPONENT_NOT_FOUND"
+            }),
+            Some("stop_game") => {
+                STOPS.fetch_add(1, Ordering::SeqCst);
+                PLAYING.store(false, Ordering::SeqCst);
+                state(false)
+            }
+            Some("get_editor_state") => state(PLAYING.load(Ordering::SeqCst)),
+            _ => json!({}),
+        })
+        .await;
+        let params = json!({ "gameObjectPath": "/Player", "fieldPath": "mass", "value": 2 });
+
+        let error = call_with_policy(
+            &config(port),
+            "set_component_field",
+            params.clone(),
+            PlayModePolicy::Fail,
+        )
+        .await
+        .expect_err("nested block should fail");
+        let message = format!("{error:#}");
+        assert!(message.contains("PLAY_MODE_BLOCKED"), "{message}");
+        assert!(message.contains("--play-mode-policy"), "{message}");
+
+        let updated = call_with_policy(
+            &config(port),
+            "set_component_field",
+            params,
+            PlayModePolicy::Stop,
+        )
+        .await
+        .expect("stop policy should retry after a nested block");
+        assert_eq!(updated["updated"], true);
+        assert_eq!(STOPS.load(Ordering::SeqCst), 1);
+
+        let error = call_with_policy(
+            &config(port),
+            "get_component_values",
+            json!({}),
+            PlayModePolicy::Stop,
+        )
+        .await
+        .expect_err("handler errors should not exit 0");
+        assert!(format!("{error:#}").contains("COMPONENT_NOT_FOUND"));
+        assert_eq!(STOPS.load(Ordering::SeqCst), 1);
+
+        server.abort();
+    }
+}
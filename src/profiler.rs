@@ -0,0 +1,593 @@
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Map, Value};
+use tokio::time::sleep;
+
+use crate::cli::TraceFormat;
+use crate::config::RuntimeConfig;
+use crate::transport::UnityClient;
+
+impl TraceFormat {
+    /// Format implied by an output file name: `.csv` is csv,
+    /// `.speedscope.json` is speedscope, anything else chrome.
+    pub fn for_path(path: &Path) -> Self {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_default();
+        if name.ends_with(".csv") {
+            Self::Csv
+        } else if name.ends_with(".speedscope.json") {
+            Self::Speedscope
+        } else {
+            Self::Chrome
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Chrome => "chrome",
+            Self::Speedscope => "speedscope",
+            Self::Csv => "csv",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Chrome | Self::Speedscope => "json",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordOptions {
+    pub duration: Duration,
+    /// Metrics to sample; empty means every metric the bridge lists.
+    pub metrics: Vec<String>,
+    pub interval: Duration,
+    /// Deep profiling instead of normal.
+    pub deep: bool,
+    /// Also let the editor save a Profiler `.data` file.
+    pub save_data: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metric {
+    pub name: String,
+    pub category: String,
+    pub unit: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    /// Microseconds since recording started.
+    pub at_us: u64,
+    /// One value per metric, `None` when the editor had no reading.
+    pub values: Vec<Option<f64>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Recording {
+    pub metrics: Vec<Metric>,
+    pub samples: Vec<Sample>,
+    pub elapsed: Duration,
+    pub interrupted: bool,
+    /// `profiler_stop` result (`sessionId`, `frameCount`, `outputPath`, ...).
+    pub session: Value,
+}
+
+/// Runs `profiler_start`, samples `profiler_get_metrics` every
+/// `options.interval` for the duration (the bridge only reports current
+/// values), then `profiler_stop` and a last `profiler_get_metrics`. The
+/// profiler is stopped even when sampling fails or Ctrl-C is pressed.
+pub async fn record(config: &RuntimeConfig, options: &RecordOptions) -> Result<Recording> {
+    let listed = available_metrics(config).await?;
+    let mut metrics = if options.metrics.is_empty() {
+        listed.clone()
+    } else {
+        options
+            .metrics
+            .iter()
+            .map(|name| {
+                listed
+                    .iter()
+                    .find(|metric| &metric.name == name)
+                    .cloned()
+                    .unwrap_or_else(|| Metric {
+                        name: name.clone(),
+                        category: "Other".to_string(),
+                        unit: String::new(),
+                    })
+            })
+            .collect()
+    };
+    if metrics.is_empty() {
+        bail!("profiler_get_metrics listed no metrics; pass --metrics");
+    }
+    let names = metrics
+        .iter()
+        .map(|metric| metric.name.clone())
+        .collect::<Vec<_>>();
+
+    call(
+        config,
+        "profiler_start",
+        json!({
+            "mode": if options.deep { "deep" } else { "normal" },
+            "recordToFile": options.save_data,
+            "metrics": names,
+        }),
+    )
+    .await?;
+    let started = Instant::now();
+
+    let mut samples = Vec::new();
+    let mut interrupted = false;
+    let sampled = async {
+        loop {
+            samples.push(sample(config, &names, &mut metrics, started).await?);
+            let remaining = options.duration.saturating_sub(started.elapsed());
+            if remaining.is_zero() {
+                return anyhow::Ok(());
+            }
+            tokio::select! {
+                _ = sleep(options.interval.min(remaining)) => {}
+                _ = tokio::signal::ctrl_c() => {
+                    interrupted = true;
+                    return Ok(());
+                }
+            }
+        }
+    }
+    .await;
+
+    let stopped = call(config, "profiler_stop", json!({})).await;
+    sampled?;
+    let session = stopped.context("Failed to stop the profiler")?;
+    let session = session.get("lastResult").cloned().unwrap_or(session);
+    let elapsed = started.elapsed();
+    let mut last = sample(config, &names, &mut metrics, started).await?;
+    // Without `recordToFile` the stop result carries the session recorders'
+    // values, the only ones gathered over the whole recording; the per-sample
+    // readings come from recorders created just before they are read.
+    apply_readings(&session, &mut metrics, &mut last.values);
+    samples.push(last);
+
+    Ok(Recording {
+        metrics,
+        samples,
+        elapsed,
+        interrupted,
+        session,
+    })
+}
+
+/// Metrics listed by `profiler_get_metrics listAvailable`. Categories map to
+/// either plain names or `{name, unit}` objects depending on bridge version.
+async fn available_metrics(config: &RuntimeConfig) -> Result<Vec<Metric>> {
+    let result = call(
+        config,
+        "profiler_get_metrics",
+        json!({ "listAvailable": true }),
+    )
+    .await?;
+    let mut metrics = Vec::new();
+    for (category, entries) in result
+        .get("categories")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+    {
+        let entries = entries
+            .get("metrics")
+            .unwrap_or(entries)
+            .as_array()
+            .into_iter()
+            .flatten();
+        for entry in entries {
+            let (name, unit) = match entry {
+                Value::String(name) => (name.as_str(), ""),
+                _ => (
+                    entry.get("name").and_then(Value::as_str).unwrap_or(""),
+                    entry.get("unit").and_then(Value::as_str).unwrap_or(""),
+                ),
+            };
+            if !name.is_empty() {
+                metrics.push(Metric {
+                    name: name.to_string(),
+                    category: category.clone(),
+                    unit: unit.to_string(),
+                });
+            }
+        }
+    }
+    Ok(metrics)
+}
+
+/// Reads the current values and fills in units the listing did not have.
+async fn sample(
+    config: &RuntimeConfig,
+    names: &[String],
+    metrics: &mut [Metric],
+    started: Instant,
+) -> Result<Sample> {
+    let at_us = started.elapsed().as_micros() as u64;
+    let result = call(config, "profiler_get_metrics", json!({ "metrics": names })).await?;
+    let mut values = vec![None; metrics.len()];
+    apply_readings(&result, metrics, &mut values);
+    Ok(Sample { at_us, values })
+}
+
+/// Copies the `metrics: [{name, value, unit}]` readings of a bridge result
+/// into `values`, matched by name, and fills in units the listing did not have.
+fn apply_readings(result: &Value, metrics: &mut [Metric], values: &mut [Option<f64>]) {
+    for reading in result
+        .get("metrics")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let name = reading.get("name").and_then(Value::as_str).unwrap_or("");
+        let Some(index) = metrics.iter().position(|metric| metric.name == name) else {
+            continue;
+        };
+        if let Some(value) = reading.get("value").and_then(Value::as_f64) {
+            values[index] = Some(value);
+        }
+        if metrics[index].unit.is_empty() {
+            if let Some(unit) = reading.get("unit").and_then(Value::as_str) {
+                metrics[index].unit = unit.to_string();
+            }
+        }
+    }
+}
+
+impl Recording {
+    pub fn render(&self, format: TraceFormat) -> Result<String> {
+        match format {
+            TraceFormat::Chrome => Ok(serde_json::to_string_pretty(&self.chrome())?),
+            TraceFormat::Speedscope => Ok(serde_json::to_string_pretty(&self.speedscope())?),
+            TraceFormat::Csv => Ok(self.csv()),
+        }
+    }
+
+    /// Counter events per metric plus one span covering the recording.
+    fn chrome(&self) -> Value {
+        let mut events = vec![
+            json!({
+                "name": "process_name",
+                "ph": "M",
+                "pid": 1,
+                "tid": 1,
+                "args": { "name": "Unity Editor" },
+            }),
+            json!({
+                "name": "profiler record",
+                "cat": "session",
+                "ph": "X",
+                "ts": 0,
+                "dur": self.elapsed.as_micros() as u64,
+                "pid": 1,
+                "tid": 1,
+                "args": self.session,
+            }),
+        ];
+        for sample in &self.samples {
+            for (metric, value) in self.metrics.iter().zip(&sample.values) {
+                let Some(value) = value else {
+                    continue;
+                };
+                let mut args = Map::new();
+                args.insert(unit_or_value(&metric.unit).to_string(), json!(value));
+                events.push(json!({
+                    "name": metric.name,
+                    "cat": metric.category,
+                    "ph": "C",
+                    "ts": sample.at_us,
+                    "pid": 1,
+                    "tid": 1,
+                    "args": args,
+                }));
+            }
+        }
+        json!({ "traceEvents": events, "displayTimeUnit": "ms" })
+    }
+
+    /// One sampled profile per unit: every reading becomes a
+    /// `category;metric` stack weighted by its value, so the flame graph
+    /// shows where time, memory or counts went over the recording.
+    fn speedscope(&self) -> Value {
+        let mut frames = Vec::<String>::new();
+        let mut frame = |name: &str| match frames.iter().position(|known| known == name) {
+            Some(index) => index,
+            None => {
+                frames.push(name.to_string());
+                frames.len() - 1
+            }
+        };
+        let mut profiles = Vec::<(&'static str, Vec<Value>, Vec<f64>)>::new();
+        for sample in &self.samples {
+            for (metric, value) in self.metrics.iter().zip(&sample.values) {
+                let Some(value) = value.filter(|value| *value > 0.0) else {
+                    continue;
+                };
+                let unit = speedscope_unit(&metric.unit);
+                let stack = json!([frame(&metric.category), frame(&metric.name)]);
+                match profiles.iter_mut().find(|(known, _, _)| *known == unit) {
+                    Some((_, stacks, weights)) => {
+                        stacks.push(stack);
+                        weights.push(value);
+                    }
+                    None => profiles.push((unit, vec![stack], vec![value])),
+                }
+            }
+        }
+        let profiles = profiles
+            .into_iter()
+            .map(|(unit, samples, weights)| {
+                json!({
+                    "type": "sampled",
+                    "name": format!("Unity metrics ({unit})"),
+                    "unit": unit,
+                    "startValue": 0,
+                    "endValue": weights.iter().sum::<f64>(),
+                    "samples": samples,
+                    "weights": weights,
+                })
+            })
+            .collect::<Vec<_>>();
+        json!({
+            "$schema": "https://www.speedscope.app/file-format-schema.json",
+            "name": "unity-cli profiler record",
+            "exporter": concat!("unity-cli ", env!("CARGO_PKG_VERSION")),
+            "activeProfileIndex": 0,
+            "shared": {
+                "frames": frames.iter().map(|name| json!({ "name": name })).collect::<Vec<_>>(),
+            },
+            "profiles": profiles,
+        })
+    }
+
+    fn csv(&self) -> String {
+        let mut out = String::from("elapsed_ms");
+        for metric in &self.metrics {
+            out.push(',');
+            let header = if metric.unit.is_empty() {
+                metric.name.clone()
+            } else {
+                format!("{} ({})", metric.name, metric.unit)
+            };
+            out.push_str(&csv_field(&header));
+        }
+        out.push('\n');
+        for sample in &self.samples {
+            out.push_str(&format!("{:.3}", sample.at_us as f64 / 1000.0));
+            for value in &sample.values {
+                out.push(',');
+                if let Some(value) = value {
+                    out.push_str(&value.to_string());
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn unit_or_value(unit: &str) -> &str {
+    if unit.is_empty() {
+        "value"
+    } else {
+        unit
+    }
+}
+
+fn speedscope_unit(unit: &str) -> &'static str {
+    match unit {
+        "bytes" => "bytes",
+        "milliseconds" => "milliseconds",
+        "microseconds" => "microseconds",
+        "nanoseconds" => "nanoseconds",
+        "seconds" => "seconds",
+        _ => "none",
+    }
+}
+
+fn csv_field(raw: &str) -> String {
+    if raw.contains([',', '"', '\n']) {
+        format!("\"{}\"", raw.replace('"', "\"\""))
+    } else {
+        raw.to_string()
+    }
+}
+
+/// Calls a profiler tool on a fresh connection; the bridge reports failures
+/// as an `error`/`code` pair inside a successful response.
+async fn call(config: &RuntimeConfig, tool_name: &str, params: Value) -> Result<Value> {
+    let mut client = UnityClient::connect(config).await.with_context(|| {
+        format!(
+            "Failed to connect to Unity at {}:{}",
+            config.host, config.port
+        )
+    })?;
+    let result = client.call_tool(tool_name, params).await?;
+    if let Some(error) = result.get("error").and_then(Value::as_str) {
+        match result.get("code").and_then(Value::as_str) {
+            Some(code) => bail!("{tool_name} failed ({code}): {error}"),
+            None => bail!("{tool_name} failed: {error}"),
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{record, Metric, RecordOptions, Recording, Sample, TraceFormat};
+    use crate::test_support::{config, spawn_editor};
+    use serde_json::{json, Value};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    fn recording() -> Recording {
+        Recording {
+            metrics: vec![
+                Metric {
+                    name: "Scripts Update Time".to_string(),
+                    category: "Scripts".to_string(),
+                    unit: "milliseconds".to_string(),
+                },
+                Metric {
+                    name: "Draw Calls Count".to_string(),
+                    category: "Rendering".to_string(),
+                    unit: "count".to_string(),
+                },
+            ],
+            samples: vec![
+                Sample {
+                    at_us: 0,
+                    values: vec![Some(4.0), Some(120.0)],
+                },
+                Sample {
+                    at_us: 250_000,
+                    values: vec![Some(6.5), None],
+                },
+            ],
+            elapsed: Duration::from_millis(250),
+            interrupted: false,
+            session: json!({ "sessionId": "abc", "frameCount": 15 }),
+        }
+    }
+
+    #[test]
+    fn renders_chrome_speedscope_and_csv() {
+        let recording = recording();
+
+        let chrome: Value =
+            serde_json::from_str(&recording.render(TraceFormat::Chrome).expect("chrome"))
+                .expect("chrome trace should be JSON");
+        let counters = chrome["traceEvents"]
+            .as_array()
+            .expect("events")
+            .iter()
+            .filter(|event| event["ph"] == "C")
+            .collect::<Vec<_>>();
+        assert_eq!(counters.len(), 3);
+        assert_eq!(counters[2]["ts"], 250_000);
+        assert_eq!(counters[2]["args"]["milliseconds"], 6.5);
+        assert_eq!(chrome["traceEvents"][1]["args"]["frameCount"], 15);
+
+        let speedscope: Value = serde_json::from_str(
+            &recording
+                .render(TraceFormat::Speedscope)
+                .expect("speedscope"),
+        )
+        .expect("speedscope file should be JSON");
+        assert_eq!(
+            speedscope["shared"]["frames"][1]["name"],
+            "Scripts Update Time"
+        );
+        let profiles = speedscope["profiles"].as_array().expect("profiles");
+        assert_eq!(profiles.len(), 2);
+        assert_eq!(profiles[0]["unit"], "milliseconds");
+        assert_eq!(profiles[0]["samples"], json!([[0, 1], [0, 1]]));
+        assert_eq!(profiles[0]["endValue"], 10.5);
+        assert_eq!(profiles[1]["unit"], "none");
+
+        assert_eq!(
+            recording.render(TraceFormat::Csv).expect("csv"),
+            "elapsed_ms,Scripts Update Time (milliseconds),Draw Calls Count (count)\n\
+             0.000,4,120\n\
+             250.000,6.5,\n"
+        );
+        assert_eq!(
+            TraceFormat::for_path(Path::new("out/Metrics.CSV")),
+            TraceFormat::Csv
+        );
+        assert_eq!(
+            TraceFormat::for_path(Path::new("frame.speedscope.json")),
+            TraceFormat::Speedscope
+        );
+        assert_eq!(
+            TraceFormat::for_path(Path::new("trace.json")),
+            TraceFormat::Chrome
+        );
+    }
+
+    #[tokio::test]
+    async fn record_samples_then_stops_and_reads_metrics() {
+        static SAMPLES: AtomicUsize = AtomicUsize::new(0);
+        static STOPS: AtomicUsize = AtomicUsize::new(0);
+        let (port, server) = spawn_editor(|request| {
+            let params = &request["params"];
+            match request["type"].as_str() {
+                Some("profiler_get_metrics") if params["listAvailable"] == true => json!({
+                    "categories": {
+                        "Memory": ["System Used Memory"],
+                        "Rendering": ["Draw Calls Count"]
+                    }
+                }),
+                Some("profiler_get_metrics") => {
+                    let index = SAMPLES.fetch_add(1, Ordering::SeqCst);
+                    json!({ "metrics": [
+                        { "name": "Draw Calls Count", "value": 100 + index, "unit": "count" }
+                    ] })
+                }
+                Some("profiler_start") => {
+                    assert_eq!(params["metrics"], json!(["Draw Calls Count"]));
+                    assert_eq!(params["recordToFile"], false);
+                    json!({ "sessionId": "abc", "isRecording": true })
+                }
+                Some("profiler_stop") => {
+                    STOPS.fetch_add(1, Ordering::SeqCst);
+                    json!({
+                        "sessionId": "abc", "duration": 0.2, "frameCount": 12,
+                        "metrics": [{ "name": "Draw Calls Count", "value": 87, "unit": "count" }]
+                    })
+                }
+                _ => json!({ "error": "unexpected", "code": "E_UNKNOWN" }),
+            }
+        })
+        .await;
+
+        let options = RecordOptions {
+            duration: Duration::from_millis(200),
+            metrics: vec!["Draw Calls Count".to_string()],
+            interval: Duration::from_millis(50),
+            deep: false,
+            save_data: false,
+        };
+        let recording = record(&config(port), &options)
+            .await
+            .expect("recording should succeed");
+
+        assert_eq!(STOPS.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            recording.metrics,
+            vec![Metric {
+                name: "Draw Calls Count".to_string(),
+                category: "Rendering".to_string(),
+                unit: "count".to_string(),
+            }]
+        );
+        assert!(recording.samples.len() >= 4, "{:?}", recording.samples);
+        assert_eq!(recording.samples[0].values, vec![Some(100.0)]);
+        assert!(recording
+            .samples
+            .windows(2)
+            .all(|pair| pair[0].at_us <= pair[1].at_us));
+        assert_eq!(recording.session["frameCount"], 12);
+        // The session recorders' values from `profiler_stop` land in the
+        // final sample, so every format carries them.
+        let last = recording.samples.last().expect("final sample");
+        assert_eq!(last.values, vec![Some(87.0)]);
+        let csv = recording.render(TraceFormat::Csv).expect("csv");
+        assert!(csv.trim_end().ends_with(",87"), "{csv}");
+
+        server.abort();
+    }
+}
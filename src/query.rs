@@ -0,0 +1,440 @@
+use anyhow::{anyhow, bail, Result};
+use serde_json::{Map, Value};
+
+/// A small jq/JSONPath-style selector used by `--query`.
+///
+/// Supported syntax: `.a.b`, `$.a.b`, `.["key"]`, `[0]`, `[-1]`, `[1:3]`,
+/// `[]`/`[*]` (iterate), `..name` (recursive descent), `{a, b}` (pick fields),
+/// `length`, `keys`, and `|` to chain terms.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    stages: Vec<Stage>,
+    streams: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Stage {
+    Field(String),
+    Recursive(String),
+    Index(i64),
+    Slice(Option<i64>, Option<i64>),
+    Iterate,
+    Project(Vec<String>),
+    Length,
+    Keys,
+}
+
+impl Query {
+    pub fn parse(expr: &str) -> Result<Self> {
+        Parser::new(expr).parse()
+    }
+
+    /// Evaluates the query. Queries that iterate collect their results into
+    /// an array; other queries return the single selected value.
+    pub fn apply(&self, value: &Value) -> Result<Value> {
+        let mut current = vec![value.clone()];
+        for stage in &self.stages {
+            let mut next = Vec::new();
+            for item in current {
+                apply_stage(stage, item, &mut next)?;
+            }
+            current = next;
+        }
+
+        if self.streams {
+            Ok(Value::Array(current))
+        } else {
+            Ok(current.into_iter().next().unwrap_or(Value::Null))
+        }
+    }
+}
+
+fn apply_stage(stage: &Stage, value: Value, out: &mut Vec<Value>) -> Result<()> {
+    match stage {
+        Stage::Field(name) => out.push(match value {
+            Value::Object(mut map) => map.remove(name).unwrap_or(Value::Null),
+            Value::Null => Value::Null,
+            Value::Array(_) => bail!("Cannot select `{name}` from an array; use `[]` to iterate"),
+            other => bail!("Cannot select `{name}` from {}", type_name(&other)),
+        }),
+        Stage::Recursive(name) => collect_recursive(&value, name, out),
+        Stage::Index(index) => out.push(match value {
+            Value::Array(items) => resolve_index(*index, items.len())
+                .and_then(|idx| items.into_iter().nth(idx))
+                .unwrap_or(Value::Null),
+            Value::Null => Value::Null,
+            other => bail!("Cannot index {} with a number", type_name(&other)),
+        }),
+        Stage::Slice(start, end) => out.push(match value {
+            Value::Array(items) => {
+                let len = items.len();
+                let start = clamp_bound(*start, len, 0);
+                let end = clamp_bound(*end, len, len).max(start);
+                Value::Array(items[start..end].to_vec())
+            }
+            Value::Null => Value::Null,
+            other => bail!("Cannot slice {}", type_name(&other)),
+        }),
+        Stage::Iterate => match value {
+            Value::Array(items) => out.extend(items),
+            Value::Object(map) => out.extend(map.into_iter().map(|(_, value)| value)),
+            Value::Null => {}
+            other => bail!("Cannot iterate over {}", type_name(&other)),
+        },
+        Stage::Project(fields) => match value {
+            Value::Object(map) => {
+                let picked = fields
+                    .iter()
+                    .map(|field| {
+                        (
+                            field.clone(),
+                            map.get(field).cloned().unwrap_or(Value::Null),
+                        )
+                    })
+                    .collect::<Map<_, _>>();
+                out.push(Value::Object(picked));
+            }
+            other => bail!("Cannot pick fields from {}", type_name(&other)),
+        },
+        Stage::Length => out.push(Value::from(match &value {
+            Value::Array(items) => items.len(),
+            Value::Object(map) => map.len(),
+            Value::String(text) => text.chars().count(),
+            Value::Null => 0,
+            other => bail!("{} has no length", type_name(other)),
+        })),
+        Stage::Keys => match value {
+            Value::Object(map) => out.push(Value::from(map.keys().cloned().collect::<Vec<_>>())),
+            Value::Array(items) => out.push(Value::from((0..items.len()).collect::<Vec<_>>())),
+            other => bail!("{} has no keys", type_name(&other)),
+        },
+    }
+    Ok(())
+}
+
+fn collect_recursive(value: &Value, name: &str, out: &mut Vec<Value>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(found) = map.get(name) {
+                out.push(found.clone());
+            }
+            for child in map.values() {
+                collect_recursive(child, name, out);
+            }
+        }
+        Value::Array(items) => {
+            for child in items {
+                collect_recursive(child, name, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn resolve_index(index: i64, len: usize) -> Option<usize> {
+    let resolved = if index < 0 { len as i64 + index } else { index };
+    (0..len as i64)
+        .contains(&resolved)
+        .then_some(resolved as usize)
+}
+
+fn clamp_bound(bound: Option<i64>, len: usize, default: usize) -> usize {
+    match bound {
+        None => default,
+        Some(value) if value < 0 => (len as i64 + value).max(0) as usize,
+        Some(value) => (value as usize).min(len),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+struct Parser<'a> {
+    expr: &'a str,
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(expr: &'a str) -> Self {
+        Self {
+            expr,
+            chars: expr.chars().collect(),
+            pos: 0,
+        }
+    }
+
+    fn parse(mut self) -> Result<Query> {
+        let mut stages = Vec::new();
+        loop {
+            self.skip_whitespace();
+            self.parse_term(&mut stages)?;
+            self.skip_whitespace();
+            match self.peek() {
+                None => break,
+                Some('|') => self.pos += 1,
+                Some(ch) => return Err(self.error(&format!("unexpected `{ch}`"))),
+            }
+        }
+
+        let streams = stages
+            .iter()
+            .any(|stage| matches!(stage, Stage::Iterate | Stage::Recursive(_)));
+        Ok(Query { stages, streams })
+    }
+
+    fn parse_term(&mut self, stages: &mut Vec<Stage>) -> Result<()> {
+        match self.peek() {
+            Some('{') => {
+                self.pos += 1;
+                stages.push(Stage::Project(self.parse_fields()?));
+                return Ok(());
+            }
+            Some(ch) if is_ident_start(ch) => {
+                let ident = self.parse_ident();
+                match ident.as_str() {
+                    "length" => stages.push(Stage::Length),
+                    "keys" => stages.push(Stage::Keys),
+                    // JSONPath-style paths may omit the leading `$.`.
+                    _ => stages.push(Stage::Field(ident)),
+                }
+            }
+            Some('$') => self.pos += 1,
+            Some('.') | Some('[') => {}
+            _ => return Err(self.error("expected a path, `{...}`, `length` or `keys`")),
+        }
+        self.parse_path(stages)
+    }
+
+    fn parse_path(&mut self, stages: &mut Vec<Stage>) -> Result<()> {
+        loop {
+            match self.peek() {
+                Some('.') if self.peek_at(1) == Some('.') => {
+                    self.pos += 2;
+                    if !self.peek().is_some_and(is_ident_start) {
+                        return Err(self.error("expected a field name after `..`"));
+                    }
+                    stages.push(Stage::Recursive(self.parse_ident()));
+                }
+                Some('.') => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some(ch) if is_ident_start(ch) => {
+                            stages.push(Stage::Field(self.parse_ident()))
+                        }
+                        Some('"') => stages.push(Stage::Field(self.parse_quoted('"')?)),
+                        // A lone `.` (identity) or `.[`.
+                        _ => {}
+                    }
+                }
+                Some('[') => {
+                    self.pos += 1;
+                    stages.push(self.parse_bracket()?);
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn parse_bracket(&mut self) -> Result<Stage> {
+        self.skip_whitespace();
+        let stage = match self.peek() {
+            Some(']') => Stage::Iterate,
+            Some('*') => {
+                self.pos += 1;
+                Stage::Iterate
+            }
+            Some(quote @ ('"' | '\'')) => Stage::Field(self.parse_quoted(quote)?),
+            _ => {
+                let start = self.parse_int()?;
+                self.skip_whitespace();
+                if self.peek() == Some(':') {
+                    self.pos += 1;
+                    self.skip_whitespace();
+                    let end = self.parse_int()?;
+                    Stage::Slice(start, end)
+                } else {
+                    Stage::Index(start.ok_or_else(|| self.error("expected an index"))?)
+                }
+            }
+        };
+        self.skip_whitespace();
+        if self.peek() != Some(']') {
+            return Err(self.error("expected `]`"));
+        }
+        self.pos += 1;
+        Ok(stage)
+    }
+
+    fn parse_fields(&mut self) -> Result<Vec<String>> {
+        let mut fields = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('}') if !fields.is_empty() => {
+                    self.pos += 1;
+                    return Ok(fields);
+                }
+                Some(ch) if is_ident_start(ch) => fields.push(self.parse_ident()),
+                Some('"') => fields.push(self.parse_quoted('"')?),
+                _ => return Err(self.error("expected a field name")),
+            }
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some('}') => {}
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+
+    fn parse_ident(&mut self) -> String {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|ch| ch.is_alphanumeric() || ch == '_' || ch == '-')
+        {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    fn parse_quoted(&mut self, quote: char) -> Result<String> {
+        self.pos += 1;
+        let mut text = String::new();
+        while let Some(ch) = self.peek() {
+            self.pos += 1;
+            match ch {
+                '\\' => {
+                    let escaped = self
+                        .peek()
+                        .ok_or_else(|| self.error("unterminated string"))?;
+                    text.push(escaped);
+                    self.pos += 1;
+                }
+                ch if ch == quote => return Ok(text),
+                ch => text.push(ch),
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+
+    fn parse_int(&mut self) -> Result<Option<i64>> {
+        let start = self.pos;
+        if self.peek() == Some('-') {
+            self.pos += 1;
+        }
+        while self.peek().is_some_and(|ch| ch.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Ok(None);
+        }
+        let text = self.chars[start..self.pos].iter().collect::<String>();
+        text.parse()
+            .map(Some)
+            .map_err(|_| self.error(&format!("invalid index `{text}`")))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn error(&self, message: &str) -> anyhow::Error {
+        anyhow!(
+            "Invalid --query `{}` at position {}: {message}",
+            self.expr,
+            self.pos + 1
+        )
+    }
+}
+
+fn is_ident_start(ch: char) -> bool {
+    ch.is_alphabetic() || ch == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Query;
+    use serde_json::{json, Value};
+
+    fn run(expr: &str, value: &Value) -> Value {
+        Query::parse(expr)
+            .expect("query should parse")
+            .apply(value)
+            .expect("query should apply")
+    }
+
+    fn sample() -> Value {
+        json!({
+            "success": true,
+            "results": [
+                { "name": "Player", "path": "/Player", "meta": { "layer": 0 } },
+                { "name": "Enemy", "path": "/Enemy", "meta": { "layer": 8 } },
+                { "name": "Boss", "path": "/Boss", "meta": { "layer": 8 } }
+            ]
+        })
+    }
+
+    #[test]
+    fn query_selects_paths_indexes_and_slices() {
+        let value = sample();
+        assert_eq!(run(".success", &value), json!(true));
+        assert_eq!(run(".results[0].name", &value), json!("Player"));
+        assert_eq!(run("$.results[-1].meta.layer", &value), json!(8));
+        assert_eq!(run("results[1].name", &value), json!("Enemy"));
+        assert_eq!(run(".results[1:3] | length", &value), json!(2));
+        assert_eq!(run(".[\"success\"]", &value), json!(true));
+        assert_eq!(run(".missing.deeper", &value), Value::Null);
+        assert_eq!(run(".", &value), value);
+    }
+
+    #[test]
+    fn query_iterates_projects_and_recurses() {
+        let value = sample();
+        assert_eq!(
+            run(".results[].name", &value),
+            json!(["Player", "Enemy", "Boss"])
+        );
+        assert_eq!(
+            run("$.results[*] | {name, path}", &value)[1],
+            json!({ "name": "Enemy", "path": "/Enemy" })
+        );
+        assert_eq!(run("..layer", &value), json!([0, 8, 8]));
+        assert_eq!(
+            run(".results[0] | keys", &value),
+            json!(["meta", "name", "path"])
+        );
+    }
+
+    #[test]
+    fn query_reports_parse_and_type_errors() {
+        assert!(Query::parse(".results[").is_err());
+        assert!(Query::parse(".results[0] extra").is_err());
+        assert!(Query::parse("{}").is_err());
+
+        let err = Query::parse(".results.name")
+            .expect("query should parse")
+            .apply(&sample())
+            .expect_err("field on array should fail");
+        assert!(format!("{err:#}").contains("use `[]` to iterate"));
+    }
+}
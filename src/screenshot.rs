@@ -0,0 +1,201 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use base64::Engine as _;
+use serde_json::{json, Map, Value};
+
+use crate::config::RuntimeConfig;
+use crate::transport::UnityClient;
+
+/// Parameters for `capture_screenshot`; the image always comes back as base64
+/// so it can be written locally even when the editor runs elsewhere.
+#[derive(Debug, Clone)]
+pub struct CaptureOptions {
+    /// `game` or `scene`.
+    pub view: String,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+impl CaptureOptions {
+    fn params(&self) -> Value {
+        let mut params = Map::new();
+        params.insert("captureMode".to_string(), json!(self.view));
+        params.insert("encodeAsBase64".to_string(), json!(true));
+        if let Some(width) = self.width {
+            params.insert("width".to_string(), json!(width));
+        }
+        if let Some(height) = self.height {
+            params.insert("height".to_string(), json!(height));
+        }
+        Value::Object(params)
+    }
+}
+
+/// Captures a screenshot and writes it to `dest`, returning the path, the
+/// dimensions and the size of the written file.
+pub async fn capture_to(
+    config: &RuntimeConfig,
+    options: &CaptureOptions,
+    dest: &Path,
+) -> Result<Value> {
+    let mut client = UnityClient::connect(config).await.with_context(|| {
+        format!(
+            "Failed to connect to Unity at {}:{}",
+            config.host, config.port
+        )
+    })?;
+    let result = client
+        .call_tool("capture_screenshot", options.params())
+        .await?;
+    if let Some(error) = result.get("error").and_then(Value::as_str) {
+        bail!("capture_screenshot failed: {error}");
+    }
+    save(&result, dest)
+}
+
+/// Writes the image from a `capture_screenshot` result: decodes `base64Data`
+/// when present, otherwise copies the file at the reported `path` (relative
+/// paths are resolved against the project root).
+pub fn save(result: &Value, dest: &Path) -> Result<Value> {
+    let bytes = match (
+        result.get("base64Data").and_then(Value::as_str),
+        result.get("path").and_then(Value::as_str),
+    ) {
+        (Some(data), _) => base64::engine::general_purpose::STANDARD
+            .decode(data.trim())
+            .context("capture_screenshot returned invalid base64 data")?,
+        (None, Some(source)) => {
+            let mut source = PathBuf::from(source);
+            if source.is_relative() {
+                if let Ok(root) = crate::local_tools::project_root() {
+                    source = root.join(source);
+                }
+            }
+            fs::read(&source)
+                .with_context(|| format!("Failed to read screenshot {}", source.display()))?
+        }
+        (None, None) => bail!("capture_screenshot returned neither image data nor a path"),
+    };
+
+    if let Some(parent) = dest
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(dest, &bytes).with_context(|| format!("Failed to write {}", dest.display()))?;
+
+    let reported = |key: &str| result.get(key).and_then(Value::as_u64);
+    let (width, height) = match png_dimensions(&bytes) {
+        Some((width, height)) => (Some(u64::from(width)), Some(u64::from(height))),
+        None => (reported("width"), reported("height")),
+    };
+    Ok(json!({
+        "path": dest.to_string_lossy(),
+        "width": width,
+        "height": height,
+        "bytes": bytes.len(),
+    }))
+}
+
+/// Next free `shot-NNNN.png` in `dir`, continuing an existing sequence.
+pub fn next_in_sequence(dir: &Path) -> Result<PathBuf> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let last = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            entry
+                .file_name()
+                .to_str()?
+                .strip_prefix("shot-")?
+                .strip_suffix(".png")?
+                .parse::<u32>()
+                .ok()
+        })
+        .max()
+        .unwrap_or(0);
+    Ok(dir.join(format!("shot-{:04}.png", last + 1)))
+}
+
+/// Width and height from a PNG's IHDR chunk.
+fn png_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    if bytes.len() < 24 || !bytes.starts_with(SIGNATURE) || &bytes[12..16] != b"IHDR" {
+        return None;
+    }
+    let read = |offset: usize| {
+        u32::from_be_bytes([
+            bytes[offset],
+            bytes[offset + 1],
+            bytes[offset + 2],
+            bytes[offset + 3],
+        ])
+    };
+    Some((read(16), read(20)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{next_in_sequence, png_dimensions, save};
+    use base64::Engine as _;
+    use serde_json::json;
+
+    fn png_header(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR".to_vec();
+        bytes.extend_from_slice(&width.to_be_bytes());
+        bytes.extend_from_slice(&height.to_be_bytes());
+        bytes.extend_from_slice(&[8, 6, 0, 0, 0]);
+        bytes
+    }
+
+    #[test]
+    fn save_decodes_base64_and_reads_png_size() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let image = png_header(640, 360);
+        let result = json!({
+            "path": "/elsewhere/image_game.png",
+            "width": 1,
+            "height": 1,
+            "base64Data": base64::engine::general_purpose::STANDARD.encode(&image)
+        });
+        let dest = dir.path().join("nested/shot.png");
+        let saved = save(&result, &dest).expect("screenshot should be saved");
+        assert_eq!(std::fs::read(&dest).expect("file should exist"), image);
+        assert_eq!(saved["width"], 640);
+        assert_eq!(saved["height"], 360);
+        assert_eq!(png_dimensions(b"GIF89a"), None);
+    }
+
+    #[test]
+    fn save_copies_reported_path_without_base64() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let source = dir.path().join("image_scene.png");
+        std::fs::write(&source, b"not a png").expect("write");
+        let result = json!({ "path": source.to_string_lossy(), "width": 800, "height": 600 });
+        let saved = save(&result, &dir.path().join("copy.png")).expect("copy should succeed");
+        assert_eq!(saved["width"], 800);
+        assert_eq!(saved["bytes"], 9);
+        assert!(save(&json!({}), &dir.path().join("none.png")).is_err());
+    }
+
+    #[test]
+    fn sequence_continues_after_highest_number() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let seq = dir.path().join("frames");
+        assert_eq!(
+            next_in_sequence(&seq).expect("next"),
+            seq.join("shot-0001.png")
+        );
+        std::fs::write(seq.join("shot-0001.png"), b"").expect("write");
+        std::fs::write(seq.join("shot-0007.png"), b"").expect("write");
+        std::fs::write(seq.join("notes.txt"), b"").expect("write");
+        assert_eq!(
+            next_in_sequence(&seq).expect("next"),
+            seq.join("shot-0008.png")
+        );
+    }
+}
@@ -0,0 +1,471 @@
+use anyhow::{anyhow, bail, Result};
+use serde_json::Value;
+
+/// A Go-template style format string used by `--format`.
+///
+/// Supports `{{.field.sub}}`, `{{.}}`, pipelines with the `upper`, `lower`,
+/// `json`, `default "x"` and `pad N` filters, `{{if .x}}..{{else}}..{{end}}`
+/// and `{{range .items}}..{{else}}..{{end}}`, inside which `.` is the element
+/// and `$` the item being rendered.
+#[derive(Debug, Clone)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Text(String),
+    Action(Pipeline),
+    If {
+        condition: Pipeline,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    Range {
+        items: Pipeline,
+        body: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct Pipeline {
+    source: Source,
+    filters: Vec<Filter>,
+}
+
+#[derive(Debug, Clone)]
+enum Source {
+    Path(Vec<String>),
+    /// `$` or `$.field`: relative to the item, even inside `range`.
+    Root(Vec<String>),
+    Literal(String),
+}
+
+#[derive(Debug, Clone)]
+enum Filter {
+    Upper,
+    Lower,
+    Json,
+    Default(String),
+    Pad(usize),
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = tokenize(source)?;
+        let mut tokens = tokens.into_iter();
+        let (nodes, terminator) = parse_nodes(&mut tokens)?;
+        if let Some(keyword) = terminator {
+            bail!("Unexpected {{{{{keyword}}}}} in --format template");
+        }
+        Ok(Self { nodes })
+    }
+
+    /// Renders one line per item. Arrays are iterated, and a response object
+    /// wrapping a single array of objects is unwrapped unless the template
+    /// refers to one of the wrapper's own fields. A trailing newline (e.g.
+    /// `{{"\n"}}` at the end of a `range` body) is dropped, so an item may
+    /// render as several lines.
+    pub fn render_lines(&self, value: &Value) -> Result<Vec<String>> {
+        let items = match value {
+            Value::Array(items) => items.iter().collect::<Vec<_>>(),
+            Value::Object(map) => {
+                let mut arrays = map.values().filter(|value| is_object_array(value));
+                let refers_to_wrapper = self.root_fields().iter().any(|key| map.contains_key(key));
+                match (arrays.next(), arrays.next(), refers_to_wrapper) {
+                    (Some(Value::Array(items)), None, false) => items.iter().collect(),
+                    _ => vec![value],
+                }
+            }
+            other => vec![other],
+        };
+
+        let mut lines = Vec::with_capacity(items.len());
+        for item in items {
+            let mut line = String::new();
+            render_nodes(&self.nodes, item, item, &mut line)?;
+            if line.ends_with('\n') {
+                line.pop();
+            }
+            lines.push(line);
+        }
+        Ok(lines)
+    }
+
+    fn root_fields(&self) -> Vec<String> {
+        let mut fields = Vec::new();
+        collect_fields(&self.nodes, false, &mut fields);
+        fields
+    }
+}
+
+/// Collects the first key of every item-relative path; inside a `range` body
+/// only `$.field` paths refer to the item.
+fn collect_fields(nodes: &[Node], nested: bool, fields: &mut Vec<String>) {
+    let push = |pipeline: &Pipeline, fields: &mut Vec<String>| {
+        let path = match &pipeline.source {
+            Source::Path(path) if !nested => path,
+            Source::Root(path) => path,
+            _ => return,
+        };
+        if let Some(first) = path.first() {
+            fields.push(first.clone());
+        }
+    };
+    for node in nodes {
+        match node {
+            Node::Text(_) => {}
+            Node::Action(pipeline) => push(pipeline, fields),
+            Node::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                push(condition, fields);
+                collect_fields(then, nested, fields);
+                collect_fields(otherwise, nested, fields);
+            }
+            Node::Range {
+                items,
+                body,
+                otherwise,
+            } => {
+                push(items, fields);
+                collect_fields(body, true, fields);
+                collect_fields(otherwise, nested, fields);
+            }
+        }
+    }
+}
+
+fn is_object_array(value: &Value) -> bool {
+    matches!(value, Value::Array(items) if !items.is_empty() && items.iter().all(Value::is_object))
+}
+
+enum Token {
+    Text(String),
+    Action(String),
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+    while let Some(open) = rest.find("{{") {
+        if open > 0 {
+            tokens.push(Token::Text(rest[..open].to_string()));
+        }
+        let after = &rest[open + 2..];
+        let close = after
+            .find("}}")
+            .ok_or_else(|| anyhow!("Unclosed `{{{{` in --format template"))?;
+        tokens.push(Token::Action(after[..close].trim().to_string()));
+        rest = &after[close + 2..];
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest.to_string()));
+    }
+    Ok(tokens)
+}
+
+/// Parses nodes until the end of input or an `else`/`end` keyword, which is
+/// returned so the caller can close its block.
+fn parse_nodes(
+    tokens: &mut impl Iterator<Item = Token>,
+) -> Result<(Vec<Node>, Option<&'static str>)> {
+    let mut nodes = Vec::new();
+    while let Some(token) = tokens.next() {
+        let action = match token {
+            Token::Text(text) => {
+                nodes.push(Node::Text(text));
+                continue;
+            }
+            Token::Action(action) => action,
+        };
+
+        match action.as_str() {
+            "else" => return Ok((nodes, Some("else"))),
+            "end" => return Ok((nodes, Some("end"))),
+            _ => {}
+        }
+
+        if let Some(condition) = action.strip_prefix("if ") {
+            let (then, otherwise) = parse_block(tokens)?;
+            nodes.push(Node::If {
+                condition: parse_pipeline(condition)?,
+                then,
+                otherwise,
+            });
+        } else if let Some(items) = action.strip_prefix("range ") {
+            let (body, otherwise) = parse_block(tokens)?;
+            nodes.push(Node::Range {
+                items: parse_pipeline(items)?,
+                body,
+                otherwise,
+            });
+        } else {
+            nodes.push(Node::Action(parse_pipeline(&action)?));
+        }
+    }
+    Ok((nodes, None))
+}
+
+/// Parses the body of an `if`/`range` block and its optional `else` branch.
+fn parse_block(tokens: &mut impl Iterator<Item = Token>) -> Result<(Vec<Node>, Vec<Node>)> {
+    let (body, terminator) = parse_nodes(tokens)?;
+    let otherwise = match terminator {
+        Some("else") => match parse_nodes(tokens)? {
+            (otherwise, Some("end")) => otherwise,
+            _ => bail!("Missing {{{{end}}}} in --format template"),
+        },
+        Some("end") => Vec::new(),
+        _ => bail!("Missing {{{{end}}}} in --format template"),
+    };
+    Ok((body, otherwise))
+}
+
+fn parse_pipeline(action: &str) -> Result<Pipeline> {
+    let mut parts = split_pipeline(action).into_iter();
+    let head = parts.next().unwrap_or_default();
+    let source = if head == "." {
+        Source::Path(Vec::new())
+    } else if let Some(path) = head.strip_prefix('.') {
+        Source::Path(path.split('.').map(str::to_string).collect())
+    } else if head == "$" {
+        Source::Root(Vec::new())
+    } else if let Some(path) = head.strip_prefix("$.") {
+        Source::Root(path.split('.').map(str::to_string).collect())
+    } else if let Some(literal) = parse_string_literal(&head) {
+        Source::Literal(literal)
+    } else {
+        bail!("Invalid --format action `{{{{{action}}}}}`; expected `.field`");
+    };
+
+    let filters = parts
+        .map(|part| parse_filter(&part))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Pipeline { source, filters })
+}
+
+fn split_pipeline(action: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut in_string = false;
+    for ch in action.chars() {
+        match ch {
+            '"' => {
+                in_string = !in_string;
+                current.push(ch);
+            }
+            '|' if !in_string => parts.push(std::mem::take(&mut current).trim().to_string()),
+            _ => current.push(ch),
+        }
+    }
+    parts.push(current.trim().to_string());
+    parts
+}
+
+fn parse_filter(part: &str) -> Result<Filter> {
+    let (name, arg) = match part.split_once(char::is_whitespace) {
+        Some((name, arg)) => (name, Some(arg.trim())),
+        None => (part, None),
+    };
+    Ok(match (name, arg) {
+        ("upper", None) => Filter::Upper,
+        ("lower", None) => Filter::Lower,
+        ("json", None) => Filter::Json,
+        ("default", Some(arg)) => {
+            Filter::Default(parse_string_literal(arg).unwrap_or_else(|| arg.to_string()))
+        }
+        ("pad", Some(arg)) => Filter::Pad(
+            arg.parse()
+                .map_err(|_| anyhow!("`pad` expects a width, got `{arg}`"))?,
+        ),
+        _ => bail!("Unknown --format filter `{part}`. Use upper, lower, json, default or pad."),
+    })
+}
+
+fn parse_string_literal(text: &str) -> Option<String> {
+    serde_json::from_str::<String>(text).ok()
+}
+
+fn render_nodes(nodes: &[Node], value: &Value, root: &Value, out: &mut String) -> Result<()> {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Action(pipeline) => out.push_str(&stringify(&evaluate(pipeline, value, root))),
+            Node::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                let branch = if is_truthy(&evaluate(condition, value, root)) {
+                    then
+                } else {
+                    otherwise
+                };
+                render_nodes(branch, value, root, out)?;
+            }
+            Node::Range {
+                items,
+                body,
+                otherwise,
+            } => {
+                let items = evaluate(items, value, root);
+                let elements = match &items {
+                    Value::Array(elements) => elements.iter().collect::<Vec<_>>(),
+                    Value::Object(map) => map.values().collect(),
+                    Value::Null => Vec::new(),
+                    other => bail!("`range` expects an array or object, got {other}"),
+                };
+                if elements.is_empty() {
+                    render_nodes(otherwise, value, root, out)?;
+                }
+                for element in elements {
+                    render_nodes(body, element, root, out)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn evaluate(pipeline: &Pipeline, value: &Value, root: &Value) -> Value {
+    let lookup = |start: &Value, path: &[String]| {
+        path.iter()
+            .try_fold(start, |current, key| current.get(key))
+            .cloned()
+            .unwrap_or(Value::Null)
+    };
+    let mut current = match &pipeline.source {
+        Source::Path(path) => lookup(value, path),
+        Source::Root(path) => lookup(root, path),
+        Source::Literal(text) => Value::String(text.clone()),
+    };
+
+    for filter in &pipeline.filters {
+        current = match filter {
+            Filter::Upper => Value::String(stringify(&current).to_uppercase()),
+            Filter::Lower => Value::String(stringify(&current).to_lowercase()),
+            Filter::Json => Value::String(current.to_string()),
+            Filter::Default(fallback) => match &current {
+                Value::Null => Value::String(fallback.clone()),
+                Value::String(text) if text.is_empty() => Value::String(fallback.clone()),
+                _ => current,
+            },
+            Filter::Pad(width) => {
+                let text = stringify(&current);
+                let pad = width.saturating_sub(text.chars().count());
+                Value::String(format!("{text}{}", " ".repeat(pad)))
+            }
+        };
+    }
+    current
+}
+
+fn stringify(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(flag) => *flag,
+        Value::Number(number) => number.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(text) => !text.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Template;
+    use serde_json::{json, Value};
+
+    fn render(template: &str, value: &Value) -> String {
+        Template::parse(template)
+            .expect("template should parse")
+            .render_lines(value)
+            .expect("template should render")
+            .join("\n")
+    }
+
+    #[test]
+    fn template_iterates_arrays_and_unwraps_results() {
+        // `find_symbol` output: one result per file, symbols nested inside.
+        let value = json!({
+            "success": true,
+            "results": [
+                {
+                    "path": "Assets/Scripts/MyClass.cs",
+                    "symbols": [
+                        { "name": "MyClass", "kind": "class", "line": 3, "column": 14 },
+                        { "name": "MyClass", "kind": "constructor", "line": 7, "column": 16 }
+                    ]
+                },
+                {
+                    "path": "Assets/Editor/MyClassEditor.cs",
+                    "symbols": [
+                        { "name": "MyClass", "kind": "class", "line": 5, "column": 18 }
+                    ]
+                }
+            ]
+        });
+        assert_eq!(
+            render(
+                "{{range .symbols}}{{$.path}}:{{.line}} {{.name}}{{\"\\n\"}}{{end}}",
+                &value
+            ),
+            "Assets/Scripts/MyClass.cs:3 MyClass\n\
+             Assets/Scripts/MyClass.cs:7 MyClass\n\
+             Assets/Editor/MyClassEditor.cs:5 MyClass"
+        );
+        assert_eq!(
+            render(
+                "{{.path}}: {{range .symbols}}{{.kind}}@{{.line}} {{end}}",
+                &value
+            ),
+            "Assets/Scripts/MyClass.cs: class@3 constructor@7 \n\
+             Assets/Editor/MyClassEditor.cs: class@5 "
+        );
+        assert_eq!(render("{{.success}}", &value), "true");
+        assert_eq!(render("{{.}}", &json!(["x", "y"])), "x\ny");
+        assert_eq!(
+            render(
+                "{{range .tags}}{{.}}{{else}}none{{end}}",
+                &json!({ "tags": [] })
+            ),
+            "none"
+        );
+    }
+
+    #[test]
+    fn template_applies_filters_and_conditionals() {
+        let value =
+            json!({ "id": "localhost:6400", "status": "up", "active": true, "meta": { "a": 1 } });
+        assert_eq!(
+            render(
+                "{{if .active}}*{{else}} {{end}} {{.id | pad 16}}|{{.status | upper}}",
+                &value
+            ),
+            "* localhost:6400  |UP"
+        );
+        assert_eq!(render("{{.missing | default \"n/a\"}}", &value), "n/a");
+        assert_eq!(render("{{.meta | json}}", &value), "{\"a\":1}");
+    }
+
+    #[test]
+    fn template_rejects_malformed_input() {
+        assert!(Template::parse("{{.path").is_err());
+        assert!(Template::parse("{{if .a}}x").is_err());
+        assert!(Template::parse("{{end}}").is_err());
+        assert!(Template::parse("{{.a | shout}}").is_err());
+        assert!(Template::parse("{{path}}").is_err());
+        assert!(Template::parse("{{range .a}}x").is_err());
+    }
+}
@@ -0,0 +1,314 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One `test run`, reduced to the tests that failed at least once.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub at: String,
+    pub mode: String,
+    #[serde(default)]
+    pub total: u64,
+    #[serde(default)]
+    pub unstable: Vec<UnstableTest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnstableTest {
+    pub name: String,
+    /// `flaky` (failed, then passed on a rerun) or `failed`.
+    pub outcome: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FlakyTest {
+    pub name: String,
+    pub mode: String,
+    pub flaky: u32,
+    pub failed: u32,
+    /// Recorded runs of the same mode.
+    pub runs: u32,
+    pub last_seen: String,
+}
+
+/// Appends a completed (possibly rerun-aggregated) run to the history file.
+pub fn record_run(status: &Value) -> Result<()> {
+    let names = |key: &str, field: &str, outcome: &str| {
+        status
+            .get(key)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.get(field).and_then(Value::as_str))
+            .map(|name| UnstableTest {
+                name: name.to_string(),
+                outcome: outcome.to_string(),
+            })
+            .collect::<Vec<_>>()
+    };
+    let mut unstable = names("flaky", "testName", "flaky");
+    unstable.extend(names("failures", "testName", "failed"));
+
+    let record = RunRecord {
+        at: unix_timestamp(),
+        mode: status
+            .get("testMode")
+            .and_then(Value::as_str)
+            .unwrap_or("EditMode")
+            .to_string(),
+        total: status
+            .get("totalTests")
+            .and_then(Value::as_u64)
+            .unwrap_or(0),
+        unstable,
+    };
+
+    let path = history_path()?;
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open test history: {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(&record)?)
+        .with_context(|| format!("Failed to write test history: {}", path.display()))
+}
+
+/// Tests that failed in recorded runs, most flaky first.
+pub fn flaky_tests(limit: Option<usize>) -> Result<Vec<FlakyTest>> {
+    let path = history_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let raw = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read test history: {}", path.display()))?;
+    let records = raw
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str::<RunRecord>(line)
+                .with_context(|| format!("Failed to parse test history: {}", path.display()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(aggregate(&records, limit))
+}
+
+fn aggregate(records: &[RunRecord], limit: Option<usize>) -> Vec<FlakyTest> {
+    let mut runs_by_mode = BTreeMap::<&str, u32>::new();
+    let mut tests = BTreeMap::<(&str, &str), FlakyTest>::new();
+    for record in records {
+        *runs_by_mode.entry(&record.mode).or_default() += 1;
+        for test in &record.unstable {
+            let entry = tests
+                .entry((&record.mode, &test.name))
+                .or_insert_with(|| FlakyTest {
+                    name: test.name.clone(),
+                    mode: record.mode.clone(),
+                    flaky: 0,
+                    failed: 0,
+                    runs: 0,
+                    last_seen: String::new(),
+                });
+            match test.outcome.as_str() {
+                "flaky" => entry.flaky += 1,
+                _ => entry.failed += 1,
+            }
+            entry.last_seen = record.at.clone();
+        }
+    }
+
+    let mut tests = tests
+        .into_values()
+        .map(|mut test| {
+            test.runs = runs_by_mode.get(test.mode.as_str()).copied().unwrap_or(0);
+            test
+        })
+        .collect::<Vec<_>>();
+    tests.sort_by(|a, b| {
+        b.flaky
+            .cmp(&a.flaky)
+            .then(b.failed.cmp(&a.failed))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    if let Some(limit) = limit {
+        tests.truncate(limit);
+    }
+    tests
+}
+
+/// Keeps the rerun-aggregated result of the last `test run --rerun-failed`
+/// for `test report`, since the editor then only holds the final single-test
+/// rerun. Runs without reruns remove it, so the editor's status is used.
+pub fn save_last_run(status: &Value) -> Result<()> {
+    let path = last_run_path()?;
+    if status.get("attempts").is_none() {
+        return match fs::remove_file(&path) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+                Err(error).with_context(|| format!("Failed to remove {}", path.display()))
+            }
+            _ => Ok(()),
+        };
+    }
+    fs::write(&path, serde_json::to_string_pretty(status)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// The result saved by `save_last_run`, if the editor's completed run
+/// `editor_status` is still that run's final rerun; a later run, or a bridge
+/// that reports no `runId`, means the editor's own status is current.
+pub fn last_run_for(editor_status: &Value) -> Result<Option<Value>> {
+    let Some(run_id) = editor_status.get("runId").filter(|id| !id.is_null()) else {
+        return Ok(None);
+    };
+    Ok(last_run()?.filter(|saved| saved.get("lastRunId") == Some(run_id)))
+}
+
+/// The result saved by `save_last_run`, if any.
+fn last_run() -> Result<Option<Value>> {
+    let path = last_run_path()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let raw =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let status = serde_json::from_str(&raw)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(Some(status))
+}
+
+/// `last-test-run.json` next to the history file.
+fn last_run_path() -> Result<PathBuf> {
+    Ok(history_path()?.with_file_name("last-test-run.json"))
+}
+
+/// `UNITY_CLI_TEST_HISTORY_PATH`, else `Library/unity-cli/test-history.jsonl`
+/// in the project, else the user config directory.
+fn history_path() -> Result<PathBuf> {
+    if let Ok(raw_path) = std::env::var("UNITY_CLI_TEST_HISTORY_PATH") {
+        let trimmed = raw_path.trim();
+        if !trimmed.is_empty() {
+            let path = PathBuf::from(trimmed);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).with_context(|| {
+                    format!("Failed to create test history dir: {}", parent.display())
+                })?;
+            }
+            return Ok(path);
+        }
+    }
+
+    let dir = match crate::local_tools::project_root() {
+        Ok(root) => root.join("Library").join("unity-cli"),
+        Err(_) => dirs::config_dir()
+            .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+            .ok_or_else(|| anyhow!("Unable to resolve config directory"))?
+            .join("unity-cli"),
+    };
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create test history dir: {}", dir.display()))?;
+    Ok(dir.join("test-history.jsonl"))
+}
+
+fn unix_timestamp() -> String {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs().to_string(),
+        Err(_) => "0".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::{aggregate, last_run, last_run_for, save_last_run, RunRecord, UnstableTest};
+
+    fn run(mode: &str, at: &str, unstable: &[(&str, &str)]) -> RunRecord {
+        RunRecord {
+            at: at.to_string(),
+            mode: mode.to_string(),
+            total: 10,
+            unstable: unstable
+                .iter()
+                .map(|(name, outcome)| UnstableTest {
+                    name: name.to_string(),
+                    outcome: outcome.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn aggregate_ranks_flaky_tests_first() {
+        let records = [
+            run("PlayMode", "1", &[("A", "flaky"), ("B", "failed")]),
+            run("PlayMode", "2", &[("B", "failed"), ("C", "flaky")]),
+            run("PlayMode", "3", &[("A", "flaky")]),
+            run("EditMode", "4", &[("A", "failed")]),
+        ];
+        let ranked = aggregate(&records, None);
+        let summary = ranked
+            .iter()
+            .map(|test| {
+                (
+                    test.name.as_str(),
+                    test.mode.as_str(),
+                    test.flaky,
+                    test.failed,
+                    test.runs,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("A", "PlayMode", 2, 0, 3),
+                ("C", "PlayMode", 1, 0, 3),
+                ("B", "PlayMode", 0, 2, 3),
+                ("A", "EditMode", 0, 1, 1),
+            ]
+        );
+        assert_eq!(ranked[0].last_seen, "3");
+        assert_eq!(aggregate(&records, Some(1)).len(), 1);
+    }
+
+    #[test]
+    fn last_run_is_kept_only_after_reruns_and_used_for_the_same_run() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        std::env::set_var(
+            "UNITY_CLI_TEST_HISTORY_PATH",
+            dir.path().join("test-history.jsonl"),
+        );
+
+        let aggregated = json!({
+            "status": "completed", "failedTests": 0, "attempts": [], "runId": "r1", "lastRunId": "r3"
+        });
+        save_last_run(&aggregated).expect("aggregate should be saved");
+        assert_eq!(
+            last_run().expect("last run should load"),
+            Some(aggregated.clone())
+        );
+
+        let editor = |run_id: Value| json!({ "status": "completed", "runId": run_id });
+        assert_eq!(
+            last_run_for(&editor(json!("r3"))).expect("last run should load"),
+            Some(aggregated)
+        );
+        // A later run in the editor, or one without a runId, is newer.
+        for run_id in [json!("r4"), json!("r1"), Value::Null] {
+            assert_eq!(
+                last_run_for(&editor(run_id)).expect("last run should load"),
+                None
+            );
+        }
+
+        save_last_run(&json!({ "status": "completed" })).expect("plain run should clear it");
+        assert_eq!(last_run().expect("last run should load"), None);
+
+        std::env::remove_var("UNITY_CLI_TEST_HISTORY_PATH");
+    }
+}
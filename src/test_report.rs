@@ -0,0 +1,502 @@
+use std::fmt::Write as _;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, bail, Context, Result};
+use regex::Regex;
+use serde_json::Value;
+
+use crate::cli::ReportFormat;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    Failed,
+    Skipped,
+    Inconclusive,
+}
+
+impl Outcome {
+    fn parse(raw: &str) -> Self {
+        match raw.to_ascii_lowercase().as_str() {
+            "passed" => Self::Passed,
+            "failed" | "error" => Self::Failed,
+            "skipped" | "ignored" => Self::Skipped,
+            _ => Self::Inconclusive,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TestCase {
+    pub full_name: String,
+    pub outcome: Outcome,
+    pub duration: f64,
+    pub message: Option<String>,
+    pub stack_trace: Option<String>,
+    pub output: Option<String>,
+}
+
+impl TestCase {
+    /// Splits `Namespace.Class.Method(args)` into class and method name.
+    fn class_and_name(&self) -> (&str, &str) {
+        let head_end = self.full_name.find('(').unwrap_or(self.full_name.len());
+        match self.full_name[..head_end].rfind('.') {
+            Some(dot) => (&self.full_name[..dot], &self.full_name[dot + 1..]),
+            None => ("", &self.full_name),
+        }
+    }
+
+    /// First `file:line` in the stack trace that points into the project.
+    fn location(&self) -> Option<(String, u32)> {
+        source_location(self.stack_trace.as_deref()?)
+    }
+}
+
+/// Test results normalized from the bridge JSON or an NUnit XML file.
+#[derive(Debug, Clone, Default)]
+pub struct TestReport {
+    pub mode: Option<String>,
+    pub duration: Option<f64>,
+    pub cases: Vec<TestCase>,
+}
+
+impl TestReport {
+    /// Reads an NUnit XML file (`-testResults` from the Unity command line) or
+    /// a JSON file holding `get_test_status` output or an `exportPath` summary.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read test results: {}", path.display()))?;
+        let parsed = if content.trim_start().starts_with('<') {
+            Self::from_nunit_xml(&content)
+        } else {
+            serde_json::from_str(&content)
+                .map_err(anyhow::Error::from)
+                .and_then(|value| Self::from_json(&value))
+        };
+        parsed.with_context(|| format!("Failed to parse test results: {}", path.display()))
+    }
+
+    /// Accepts a completed `get_test_status` result or an `exportPath` summary.
+    pub fn from_json(value: &Value) -> Result<Self> {
+        if !value.is_object() {
+            bail!("Test results must be a JSON object");
+        }
+        let text = |item: &Value, keys: &[&str]| {
+            keys.iter()
+                .find_map(|key| item.get(*key).and_then(Value::as_str))
+                .filter(|text| !text.is_empty())
+                .map(str::to_string)
+        };
+        let list = |key: &str| {
+            value
+                .get(key)
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default()
+        };
+
+        let mut cases = list("tests")
+            .iter()
+            .filter_map(|test| {
+                Some(TestCase {
+                    full_name: text(test, &["fullName", "name"])?,
+                    outcome: Outcome::parse(&text(test, &["status"]).unwrap_or_default()),
+                    duration: test.get("duration").and_then(Value::as_f64).unwrap_or(0.0),
+                    message: text(test, &["message"]),
+                    stack_trace: text(test, &["stackTrace"]),
+                    output: text(test, &["output"]),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // Failures carry the stack traces; the per-test list may be absent.
+        for failure in list("failures") {
+            let Some(full_name) = text(&failure, &["testName", "fullName", "name"]) else {
+                continue;
+            };
+            match cases.iter_mut().find(|case| case.full_name == full_name) {
+                Some(case) => {
+                    case.outcome = Outcome::Failed;
+                    case.message = case.message.take().or(text(&failure, &["message"]));
+                    case.stack_trace = case.stack_trace.take().or(text(&failure, &["stackTrace"]));
+                }
+                None => cases.push(TestCase {
+                    full_name,
+                    outcome: Outcome::Failed,
+                    duration: 0.0,
+                    message: text(&failure, &["message"]),
+                    stack_trace: text(&failure, &["stackTrace"]),
+                    output: None,
+                }),
+            }
+        }
+
+        if cases.is_empty() && value.get("status").and_then(Value::as_str) == Some("running") {
+            bail!("The test run has not completed yet");
+        }
+
+        Ok(Self {
+            mode: text(value, &["testMode"]),
+            duration: value
+                .get("durationSeconds")
+                .or_else(|| value.get("elapsedSeconds"))
+                .and_then(Value::as_f64),
+            cases,
+        })
+    }
+
+    pub fn from_nunit_xml(xml: &str) -> Result<Self> {
+        let document = roxmltree::Document::parse(xml).map_err(|error| anyhow!("{error}"))?;
+        let root = document.root_element();
+        if !matches!(root.tag_name().name(), "test-run" | "test-results") {
+            bail!(
+                "Expected an NUnit <test-run> document, found <{}>",
+                root.tag_name().name()
+            );
+        }
+        let child_text = |node: roxmltree::Node, path: &[&str]| {
+            let mut current = node;
+            for name in path {
+                current = current.children().find(|child| child.has_tag_name(*name))?;
+            }
+            current
+                .text()
+                .map(str::trim)
+                .filter(|text| !text.is_empty())
+                .map(str::to_string)
+        };
+
+        let cases = root
+            .descendants()
+            .filter(|node| node.has_tag_name("test-case"))
+            .map(|node| TestCase {
+                full_name: node
+                    .attribute("fullname")
+                    .or_else(|| node.attribute("name"))
+                    .unwrap_or_default()
+                    .to_string(),
+                outcome: Outcome::parse(node.attribute("result").unwrap_or_default()),
+                duration: node
+                    .attribute("duration")
+                    .or_else(|| node.attribute("time"))
+                    .and_then(|raw| raw.parse().ok())
+                    .unwrap_or(0.0),
+                message: child_text(node, &["failure", "message"])
+                    .or_else(|| child_text(node, &["reason", "message"])),
+                stack_trace: child_text(node, &["failure", "stack-trace"]),
+                output: child_text(node, &["output"]),
+            })
+            .collect();
+
+        Ok(Self {
+            mode: None,
+            duration: root.attribute("duration").and_then(|raw| raw.parse().ok()),
+            cases,
+        })
+    }
+
+    fn count(&self, outcome: Outcome) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| case.outcome == outcome)
+            .count()
+    }
+
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Junit => self.render_junit(),
+            ReportFormat::Tap => self.render_tap(),
+            ReportFormat::Github => self.render_github(),
+        }
+    }
+
+    fn render_junit(&self) -> String {
+        let name = self.mode.as_deref().unwrap_or("Unity");
+        let skipped = self.count(Outcome::Skipped) + self.count(Outcome::Inconclusive);
+        let time = self
+            .duration
+            .unwrap_or_else(|| self.cases.iter().map(|case| case.duration).sum());
+        let counts = format!(
+            "tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{skipped}\" time=\"{time:.3}\"",
+            self.cases.len(),
+            self.count(Outcome::Failed),
+        );
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(xml, "<testsuites name=\"{}\" {counts}>", xml_escape(name));
+        let _ = writeln!(xml, "  <testsuite name=\"{}\" {counts}>", xml_escape(name));
+        for case in &self.cases {
+            let (class_name, test_name) = case.class_and_name();
+            let mut attributes = format!(
+                "name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+                xml_escape(test_name),
+                xml_escape(class_name),
+                case.duration
+            );
+            if let Some((file, line)) = case.location() {
+                let _ = write!(
+                    attributes,
+                    " file=\"{}\" line=\"{line}\"",
+                    xml_escape(&file)
+                );
+            }
+
+            let mut body = String::new();
+            match case.outcome {
+                Outcome::Failed => {
+                    let message = case.message.as_deref().unwrap_or("Test failed");
+                    let details = [case.message.as_deref(), case.stack_trace.as_deref()]
+                        .into_iter()
+                        .flatten()
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    let _ = writeln!(
+                        body,
+                        "      <failure message=\"{}\" type=\"Failure\">{}</failure>",
+                        xml_escape(first_line(message)),
+                        xml_escape(&details)
+                    );
+                }
+                Outcome::Skipped | Outcome::Inconclusive => {
+                    let _ = writeln!(
+                        body,
+                        "      <skipped message=\"{}\"/>",
+                        xml_escape(first_line(case.message.as_deref().unwrap_or("")))
+                    );
+                }
+                Outcome::Passed => {}
+            }
+            if let Some(output) = &case.output {
+                let _ = writeln!(
+                    body,
+                    "      <system-out>{}</system-out>",
+                    xml_escape(output)
+                );
+            }
+
+            if body.is_empty() {
+                let _ = writeln!(xml, "    <testcase {attributes}/>");
+            } else {
+                let _ = writeln!(xml, "    <testcase {attributes}>\n{body}    </testcase>");
+            }
+        }
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+
+    fn render_tap(&self) -> String {
+        let mut tap = format!("TAP version 13\n1..{}\n", self.cases.len());
+        for (index, case) in self.cases.iter().enumerate() {
+            let number = index + 1;
+            match case.outcome {
+                Outcome::Passed => {
+                    let _ = writeln!(tap, "ok {number} - {}", case.full_name);
+                }
+                Outcome::Skipped | Outcome::Inconclusive => {
+                    let reason = case.message.as_deref().map(first_line).unwrap_or("");
+                    let _ = writeln!(tap, "ok {number} - {} # SKIP {reason}", case.full_name);
+                }
+                Outcome::Failed => {
+                    let _ = writeln!(tap, "not ok {number} - {}", case.full_name);
+                    tap.push_str("  ---\n");
+                    if let Some(message) = &case.message {
+                        let _ = writeln!(
+                            tap,
+                            "  message: {}",
+                            serde_json::to_string(message.trim()).unwrap_or_default()
+                        );
+                    }
+                    if let Some((file, line)) = case.location() {
+                        let _ = writeln!(tap, "  at: {file}:{line}");
+                    }
+                    if let Some(stack) = &case.stack_trace {
+                        tap.push_str("  stack: |\n");
+                        for line in stack.lines() {
+                            let _ = writeln!(tap, "    {line}");
+                        }
+                    }
+                    tap.push_str("  ...\n");
+                }
+            }
+        }
+        tap
+    }
+
+    /// GitHub Actions workflow commands: one `::error` per failed test.
+    fn render_github(&self) -> String {
+        let mut out = String::new();
+        for case in self
+            .cases
+            .iter()
+            .filter(|case| case.outcome == Outcome::Failed)
+        {
+            let mut properties = Vec::new();
+            if let Some((file, line)) = case.location() {
+                properties.push(format!("file={}", github_property(&file)));
+                properties.push(format!("line={line}"));
+            }
+            properties.push(format!("title={}", github_property(&case.full_name)));
+            let _ = writeln!(
+                out,
+                "::error {}::{}",
+                properties.join(","),
+                github_data(case.message.as_deref().unwrap_or("Test failed").trim())
+            );
+        }
+        out
+    }
+}
+
+/// Finds the first stack frame with a source location outside Unity's own
+/// packages, made relative to the current directory when it lies inside it.
+fn source_location(stack_trace: &str) -> Option<(String, u32)> {
+    static FRAME: OnceLock<Regex> = OnceLock::new();
+    let frame = FRAME.get_or_init(|| {
+        Regex::new(r"(?:\bin |\()(?P<file>[^\s()][^()]*?\.cs):(?:line )?(?P<line>\d+)")
+            .expect("stack frame pattern should compile")
+    });
+    let cwd = std::env::current_dir().ok();
+    stack_trace.lines().find_map(|line| {
+        let captures = frame.captures(line)?;
+        let file = captures["file"].replace('\\', "/");
+        if file.contains("/PackageCache/") || file.contains("UnityEngine.TestRunner") {
+            return None;
+        }
+        let line = captures["line"].parse().ok()?;
+        let relative = cwd
+            .as_deref()
+            .and_then(|cwd| Path::new(&file).strip_prefix(cwd).ok())
+            .map(|path| path.to_string_lossy().replace('\\', "/"))
+            .unwrap_or(file);
+        Some((relative, line))
+    })
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("")
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters other than tab/newline are not valid XML 1.0.
+            ch if ch.is_control() && !matches!(ch, '\t' | '\n' | '\r') => {}
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Escapes the message part of a workflow command.
+fn github_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a `key=value` property of a workflow command.
+fn github_property(text: &str) -> String {
+    github_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{source_location, ReportFormat, TestReport};
+    use serde_json::json;
+
+    fn status() -> serde_json::Value {
+        json!({
+            "status": "completed",
+            "testMode": "EditMode",
+            "elapsedSeconds": 1.5,
+            "tests": [
+                { "name": "Runs", "fullName": "Game.Tests.PlayerTests.Runs", "status": "Passed", "duration": 0.01 },
+                { "name": "Jumps", "fullName": "Game.Tests.PlayerTests.Jumps", "status": "Failed", "duration": 0.02,
+                  "message": "Expected: 1\n  But was: 2" },
+                { "name": "Later", "fullName": "Game.Tests.PlayerTests.Later(\"a.b\")", "status": "Skipped",
+                  "message": "Not ready" }
+            ],
+            "failures": [
+                { "testName": "Game.Tests.PlayerTests.Jumps", "message": "Expected: 1\n  But was: 2",
+                  "stackTrace": "at Game.Tests.PlayerTests.Jumps () [0x00001] in Assets/Tests/PlayerTests.cs:42\n" }
+            ]
+        })
+    }
+
+    #[test]
+    fn junit_report_includes_failures_with_location() {
+        let report = TestReport::from_json(&status()).expect("status should convert");
+        let xml = report.render(ReportFormat::Junit);
+        assert!(xml.contains(
+            "<testsuite name=\"EditMode\" tests=\"3\" failures=\"1\" errors=\"0\" skipped=\"1\" time=\"1.500\">"
+        ));
+        assert!(xml.contains(
+            "<testcase name=\"Jumps\" classname=\"Game.Tests.PlayerTests\" time=\"0.020\" file=\"Assets/Tests/PlayerTests.cs\" line=\"42\">"
+        ));
+        assert!(xml.contains("<failure message=\"Expected: 1\" type=\"Failure\">Expected: 1\n  But was: 2\nat Game.Tests"));
+        assert!(xml.contains(
+            "<testcase name=\"Later(&quot;a.b&quot;)\" classname=\"Game.Tests.PlayerTests\""
+        ));
+        roxmltree::Document::parse(&xml).expect("JUnit output should be well-formed");
+    }
+
+    #[test]
+    fn tap_and_github_reports_describe_failures() {
+        let report = TestReport::from_json(&status()).expect("status should convert");
+        let tap = report.render(ReportFormat::Tap);
+        assert!(tap.starts_with("TAP version 13\n1..3\nok 1 - Game.Tests.PlayerTests.Runs\n"));
+        assert!(tap.contains("not ok 2 - Game.Tests.PlayerTests.Jumps\n  ---\n  message: \"Expected: 1\\n  But was: 2\"\n  at: Assets/Tests/PlayerTests.cs:42\n"));
+        assert!(tap.contains("ok 3 - Game.Tests.PlayerTests.Later(\"a.b\") # SKIP Not ready"));
+
+        let github = report.render(ReportFormat::Github);
+        assert_eq!(
+            github,
+            "::error file=Assets/Tests/PlayerTests.cs,line=42,title=Game.Tests.PlayerTests.Jumps::Expected: 1%0A  But was: 2\n"
+        );
+    }
+
+    #[test]
+    fn nunit_xml_is_converted() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<test-run id="2" testcasecount="2" result="Failed" total="2" passed="1" failed="1" duration="0.75">
+  <test-suite type="Assembly" name="Game.Tests.dll">
+    <test-case id="1" name="Runs" fullname="Game.Tests.PlayerTests.Runs" result="Passed" duration="0.1" />
+    <test-case id="2" name="Jumps" fullname="Game.Tests.PlayerTests.Jumps" result="Failed" duration="0.2">
+      <failure>
+        <message><![CDATA[Expected: True]]></message>
+        <stack-trace><![CDATA[at Game.Tests.PlayerTests.Jumps () [0x0] in /work/Project/Assets/Tests/PlayerTests.cs:17]]></stack-trace>
+      </failure>
+      <output><![CDATA[log line]]></output>
+    </test-case>
+  </test-suite>
+</test-run>"#;
+        let report = TestReport::from_nunit_xml(xml).expect("NUnit XML should parse");
+        assert_eq!(report.cases.len(), 2);
+        assert_eq!(report.duration, Some(0.75));
+        let junit = report.render(ReportFormat::Junit);
+        assert!(junit.contains("file=\"/work/Project/Assets/Tests/PlayerTests.cs\" line=\"17\""));
+        assert!(junit.contains("<system-out>log line</system-out>"));
+        assert!(TestReport::from_nunit_xml("<html/>").is_err());
+    }
+
+    #[test]
+    fn source_location_skips_unity_frames() {
+        let stack = "at NUnit.Framework.Assert.Fail () in /Unity/Library/PackageCache/com.unity.ext.nunit@2.0.3/Assert.cs:9\n\
+                     at Game.Tests.A.B () in C:\\Work\\Assets\\Tests\\A.cs:12";
+        assert_eq!(
+            source_location(stack),
+            Some(("C:/Work/Assets/Tests/A.cs".to_string(), 12))
+        );
+        assert_eq!(source_location("at Game.Tests.A.B () [0x0]"), None);
+    }
+}
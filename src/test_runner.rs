@@ -0,0 +1,603 @@
+use std::io::{IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{json, Map, Value};
+use tokio::time::sleep;
+
+use crate::config::RuntimeConfig;
+use crate::transport::UnityClient;
+
+/// How long polling tolerates a lost connection (e.g. a domain reload while
+/// PlayMode tests start) before giving up.
+const RECONNECT_GRACE_SECS: u64 = 120;
+/// Without a terminal, progress is reported as a plain line this often.
+const PLAIN_PROGRESS_INTERVAL_SECS: u64 = 30;
+
+#[derive(Debug, Clone)]
+pub struct TestRunOptions {
+    pub mode: String,
+    pub filter: Option<String>,
+    pub category: Option<String>,
+    pub namespace: Option<String>,
+    pub max_wait: Duration,
+    pub poll_interval: Duration,
+    /// Print progress to stderr while waiting.
+    pub progress: bool,
+}
+
+impl TestRunOptions {
+    fn run_params(&self) -> Value {
+        let mut params = Map::new();
+        params.insert("testMode".to_string(), Value::String(self.mode.clone()));
+        for (key, value) in [
+            ("filter", &self.filter),
+            ("category", &self.category),
+            ("namespace", &self.namespace),
+        ] {
+            if let Some(value) = value {
+                params.insert(key.to_string(), Value::String(value.clone()));
+            }
+        }
+        Value::Object(params)
+    }
+}
+
+/// Starts a test run and polls `get_test_status` until it completes.
+///
+/// Returns the `completed` status, with `elapsedSeconds` measured by the CLI.
+/// Fails when the run cannot start, e.g. on compilation errors.
+pub async fn run_tests(config: &RuntimeConfig, options: &TestRunOptions) -> Result<Value> {
+    let mut client = connect(config).await?;
+    let started = client.call_tool("run_tests", options.run_params()).await?;
+    if let Some(error) = started.get("error").and_then(Value::as_str) {
+        let code = started.get("code").and_then(Value::as_str);
+        if code == Some("COMPILATION_ERRORS") {
+            bail!(
+                "{error}{}",
+                compile_error_lines(&mut client).await.unwrap_or_default()
+            );
+        }
+        match code {
+            Some(code) => bail!("{error} (code: {code})"),
+            None => bail!("{error}"),
+        }
+    }
+
+    let started_at = Instant::now();
+    let mut progress = Progress::new(options.progress, &options.mode);
+    let mut client = Some(client);
+    let mut disconnected_since: Option<Instant> = None;
+
+    loop {
+        if started_at.elapsed() > options.max_wait {
+            progress.finish();
+            bail!(
+                "Tests did not finish within {}s; they keep running in the editor",
+                options.max_wait.as_secs()
+            );
+        }
+
+        let polled = match client.as_mut() {
+            Some(active) => active.call_tool("get_test_status", json!({})).await,
+            None => match connect(config).await {
+                Ok(fresh) => {
+                    client
+                        .insert(fresh)
+                        .call_tool("get_test_status", json!({}))
+                        .await
+                }
+                Err(error) => Err(error),
+            },
+        };
+
+        match polled {
+            Ok(status) => {
+                disconnected_since = None;
+                match status.get("status").and_then(Value::as_str) {
+                    Some("completed") => {
+                        progress.finish();
+                        let mut status = status;
+                        status["elapsedSeconds"] = json!(started_at.elapsed().as_secs_f64());
+                        return Ok(status);
+                    }
+                    Some("running") => progress.update(started_at.elapsed()),
+                    Some("idle") => {
+                        progress.finish();
+                        bail!("The editor lost track of the test run (status: idle)");
+                    }
+                    _ => {
+                        progress.finish();
+                        let message = status
+                            .get("message")
+                            .or_else(|| status.get("error"))
+                            .and_then(Value::as_str)
+                            .unwrap_or("Test run failed");
+                        match status.get("code").and_then(Value::as_str) {
+                            Some(code) => bail!("{message} (code: {code})"),
+                            None => bail!("{message}"),
+                        }
+                    }
+                }
+            }
+            Err(error) => {
+                client = None;
+                let since = *disconnected_since.get_or_insert_with(Instant::now);
+                if since.elapsed() > Duration::from_secs(RECONNECT_GRACE_SECS) {
+                    progress.finish();
+                    return Err(
+                        error.context("Lost the connection to Unity while tests were running")
+                    );
+                }
+            }
+        }
+
+        tokio::select! {
+            _ = sleep(options.poll_interval) => {}
+            _ = tokio::signal::ctrl_c() => {
+                progress.finish();
+                bail!("Interrupted; the test run keeps going in the editor");
+            }
+        }
+    }
+}
+
+/// Runs the tests, then re-runs each failing test on its own (an exact-name
+/// `filter`) up to `reruns` more times.
+///
+/// The returned status is the first run's, with the outcome of the reruns
+/// folded in: tests that later passed move from `failures` to `flaky`,
+/// `attempts` lists the failing tests of each attempt and `lastRunId` is the
+/// editor's `runId` for the final rerun, the run it reports afterwards.
+pub async fn run_with_reruns(
+    config: &RuntimeConfig,
+    options: &TestRunOptions,
+    reruns: u32,
+) -> Result<Value> {
+    let started_at = Instant::now();
+    let mut status = run_tests(config, options).await?;
+    if reruns == 0 || failed_count(&status) == 0 {
+        return Ok(status);
+    }
+
+    let mut failing = status
+        .get("failures")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    let mut attempts = vec![attempt_entry(1, &failing)];
+    let mut flaky = Vec::new();
+    let mut last_run_id = status.get("runId").cloned().unwrap_or(Value::Null);
+
+    for attempt in 2..=reruns + 1 {
+        if failing.is_empty() {
+            break;
+        }
+        let mut still_failing = Vec::new();
+        for failure in failing {
+            let Some(name) = failure.get("testName").and_then(Value::as_str) else {
+                still_failing.push(failure);
+                continue;
+            };
+            let rerun = TestRunOptions {
+                filter: Some(name.to_string()),
+                ..options.clone()
+            };
+            // A rerun that errors out (timeout, lost connection) counts as a
+            // failure; the first run's results are kept either way.
+            let result = match run_tests(config, &rerun).await {
+                Ok(result) => result,
+                Err(error) => {
+                    let mut failure = failure;
+                    failure["rerunError"] = json!(format!("{error:#}"));
+                    still_failing.push(failure);
+                    continue;
+                }
+            };
+            last_run_id = result.get("runId").cloned().unwrap_or(Value::Null);
+            let passed = result
+                .get("passedTests")
+                .and_then(Value::as_u64)
+                .unwrap_or(0);
+            // A rerun that matched nothing proves nothing; keep the test failing.
+            if passed > 0 && failed_count(&result) == 0 {
+                flaky.push(json!({ "testName": name, "passedOnAttempt": attempt }));
+            } else {
+                let latest = result
+                    .get("failures")
+                    .and_then(Value::as_array)
+                    .and_then(|failures| failures.first())
+                    .cloned();
+                still_failing.push(latest.unwrap_or(failure));
+            }
+        }
+        attempts.push(attempt_entry(attempt, &still_failing));
+        failing = still_failing;
+    }
+
+    let count = |key: &str| status.get(key).and_then(Value::as_u64).unwrap_or(0);
+    status["passedTests"] = json!(count("passedTests") + flaky.len() as u64);
+    status["failedTests"] = json!(failing.len());
+    status["flakyTests"] = json!(flaky.len());
+    status["success"] = json!(failing.is_empty());
+    if let Some(tests) = status.get_mut("tests").and_then(Value::as_array_mut) {
+        for test in tests {
+            let full_name = test.get("fullName").and_then(Value::as_str);
+            if flaky
+                .iter()
+                .any(|entry| entry.get("testName").and_then(Value::as_str) == full_name)
+            {
+                test["status"] = json!("Passed");
+                test["flaky"] = json!(true);
+            }
+        }
+    }
+    status["failures"] = Value::Array(failing);
+    status["flaky"] = Value::Array(flaky);
+    status["attempts"] = Value::Array(attempts);
+    status["lastRunId"] = last_run_id;
+    status["elapsedSeconds"] = json!(started_at.elapsed().as_secs_f64());
+    Ok(status)
+}
+
+fn attempt_entry(attempt: u32, failing: &[Value]) -> Value {
+    let names = failing
+        .iter()
+        .filter_map(|failure| failure.get("testName").cloned())
+        .collect::<Vec<_>>();
+    json!({ "attempt": attempt, "failed": names })
+}
+
+/// Number of failed tests in a `completed` status.
+pub fn failed_count(status: &Value) -> u64 {
+    status
+        .get("failedTests")
+        .and_then(Value::as_u64)
+        .unwrap_or(0)
+}
+
+/// Human-readable summary: one count line, then each failure with its message.
+pub fn render_summary(status: &Value) -> String {
+    let count = |key: &str| status.get(key).and_then(Value::as_u64).unwrap_or(0);
+    let flaky = match count("flakyTests") {
+        0 => String::new(),
+        flaky => format!("{flaky} flaky, "),
+    };
+    let mut lines = vec![format!(
+        "{}: {} passed, {flaky}{} failed, {} skipped ({} total) in {:.1}s",
+        status
+            .get("testMode")
+            .and_then(Value::as_str)
+            .unwrap_or("Tests"),
+        count("passedTests"),
+        count("failedTests"),
+        count("skippedTests") + count("inconclusiveTests"),
+        count("totalTests"),
+        status
+            .get("elapsedSeconds")
+            .and_then(Value::as_f64)
+            .unwrap_or(0.0),
+    )];
+
+    for entry in status
+        .get("flaky")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        lines.push(format!(
+            "FLAKY {} (passed on attempt {})",
+            entry
+                .get("testName")
+                .and_then(Value::as_str)
+                .unwrap_or("(unnamed test)"),
+            entry
+                .get("passedOnAttempt")
+                .and_then(Value::as_u64)
+                .unwrap_or(0)
+        ));
+    }
+
+    for failure in status
+        .get("failures")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let name = failure
+            .get("testName")
+            .and_then(Value::as_str)
+            .unwrap_or("(unnamed test)");
+        lines.push(format!("FAILED {name}"));
+        if let Some(message) = failure.get("message").and_then(Value::as_str) {
+            lines.extend(
+                message
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(|line| format!("    {}", line.trim_end())),
+            );
+        }
+    }
+    lines.join("\n")
+}
+
+/// Formats the current compile errors as indented lines, for the
+/// `COMPILATION_ERRORS` pre-check failure.
+async fn compile_error_lines(client: &mut UnityClient) -> Option<String> {
+    let state = client
+        .call_tool("get_compilation_state", json!({ "includeMessages": true }))
+        .await
+        .ok()?;
+    let lines = state
+        .get("messages")
+        .and_then(Value::as_array)?
+        .iter()
+        .filter(|message| message.get("type").and_then(Value::as_str) == Some("Error"))
+        .map(|message| {
+            let text = message.get("message").and_then(Value::as_str).unwrap_or("");
+            match (
+                message.get("file").and_then(Value::as_str),
+                message.get("line").and_then(Value::as_u64),
+            ) {
+                (Some(file), Some(line)) if !file.is_empty() => {
+                    format!("\n  {file}:{line}: {text}")
+                }
+                _ => format!("\n  {text}"),
+            }
+        })
+        .collect::<String>();
+    Some(lines)
+}
+
+async fn connect(config: &RuntimeConfig) -> Result<UnityClient> {
+    UnityClient::connect(config).await.with_context(|| {
+        format!(
+            "Failed to connect to Unity at {}:{}",
+            config.host, config.port
+        )
+    })
+}
+
+/// Progress on stderr: a rewritten line on a terminal, occasional plain lines
+/// otherwise.
+struct Progress {
+    enabled: bool,
+    terminal: bool,
+    mode: String,
+    last_plain: Option<Instant>,
+}
+
+impl Progress {
+    fn new(enabled: bool, mode: &str) -> Self {
+        Self {
+            enabled,
+            terminal: std::io::stderr().is_terminal(),
+            mode: mode.to_string(),
+            last_plain: None,
+        }
+    }
+
+    fn update(&mut self, elapsed: Duration) {
+        if !self.enabled {
+            return;
+        }
+        let line = format!("running {} tests... {}s", self.mode, elapsed.as_secs());
+        if self.terminal {
+            eprint!("\r\x1b[K{line}");
+            let _ = std::io::stderr().flush();
+        } else if self
+            .last_plain
+            .is_none_or(|last| last.elapsed() >= Duration::from_secs(PLAIN_PROGRESS_INTERVAL_SECS))
+        {
+            eprintln!("{line}");
+            self.last_plain = Some(Instant::now());
+        }
+    }
+
+    fn finish(&mut self) {
+        if self.enabled && self.terminal {
+            eprint!("\r\x1b[K");
+            let _ = std::io::stderr().flush();
+        }
+    }
+}
+
+pub fn parse_mode(raw: &str) -> Result<String> {
+    ["EditMode", "PlayMode", "All"]
+        .into_iter()
+        .find(|mode| mode.eq_ignore_ascii_case(raw))
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("Invalid test mode `{raw}`. Use EditMode, PlayMode or All."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        failed_count, parse_mode, render_summary, run_tests, run_with_reruns, TestRunOptions,
+    };
+    use crate::test_support::{config, spawn_editor};
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    fn options() -> TestRunOptions {
+        TestRunOptions {
+            mode: "EditMode".to_string(),
+            filter: Some("Game.Tests".to_string()),
+            category: None,
+            namespace: None,
+            max_wait: Duration::from_secs(5),
+            poll_interval: Duration::from_millis(10),
+            progress: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn run_tests_polls_until_completed_and_summarizes_failures() {
+        static POLLS: AtomicUsize = AtomicUsize::new(0);
+        let (port, server) = spawn_editor(|request| match request["type"].as_str() {
+            Some("run_tests") => {
+                assert_eq!(request["params"]["testMode"], "EditMode");
+                assert_eq!(request["params"]["filter"], "Game.Tests");
+                json!({ "status": "running", "runId": "r1" })
+            }
+            Some("get_test_status") if POLLS.fetch_add(1, Ordering::SeqCst) < 2 => {
+                json!({ "status": "running", "runId": "r1" })
+            }
+            Some("get_test_status") => json!({
+                "status": "completed",
+                "testMode": "EditMode",
+                "totalTests": 3,
+                "passedTests": 2,
+                "failedTests": 1,
+                "skippedTests": 0,
+                "failures": [
+                    { "testName": "Game.Tests.Fails", "message": "Expected: 1\n  But was: 2\n" }
+                ]
+            }),
+            _ => json!({}),
+        })
+        .await;
+
+        let status = run_tests(&config(port), &options())
+            .await
+            .expect("run should complete");
+        assert_eq!(POLLS.load(Ordering::SeqCst), 3);
+        assert_eq!(failed_count(&status), 1);
+
+        let summary = render_summary(&status);
+        assert!(summary.starts_with("EditMode: 2 passed, 1 failed, 0 skipped (3 total) in "));
+        assert!(summary.contains("FAILED Game.Tests.Fails\n    Expected: 1\n      But was: 2"));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn run_tests_reports_compilation_errors_from_precheck() {
+        let (port, server) = spawn_editor(|request| match request["type"].as_str() {
+            Some("run_tests") => json!({
+                "error": "Cannot run tests because the last compilation has 1 error(s).",
+                "code": "COMPILATION_ERRORS"
+            }),
+            Some("get_compilation_state") => json!({
+                "messages": [
+                    { "type": "Error", "message": "CS0103: The name 'x' does not exist", "file": "Assets/A.cs", "line": 7 },
+                    { "type": "Warning", "message": "CS0168: unused", "file": "Assets/B.cs", "line": 2 }
+                ]
+            }),
+            _ => json!({}),
+        })
+        .await;
+
+        let error = run_tests(&config(port), &options())
+            .await
+            .expect_err("compilation errors should fail the run");
+        let message = format!("{error:#}");
+        assert!(message.contains("1 error(s)"));
+        assert!(message.contains("Assets/A.cs:7: CS0103"));
+        assert!(!message.contains("CS0168"));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn run_with_reruns_classifies_flaky_and_failed_tests() {
+        static FILTER: Mutex<Option<String>> = Mutex::new(None);
+        static RERUNS: AtomicUsize = AtomicUsize::new(0);
+        let (port, server) = spawn_editor(|request| match request["type"].as_str() {
+            Some("run_tests") => {
+                let filter = request["params"]["filter"].as_str().map(str::to_string);
+                if filter.as_deref() != Some("Game.Tests") {
+                    RERUNS.fetch_add(1, Ordering::SeqCst);
+                }
+                *FILTER.lock().expect("filter lock") = filter;
+                json!({ "status": "running" })
+            }
+            Some("get_test_status") => {
+                let filter = FILTER.lock().expect("filter lock").clone();
+                let failure = |name: &str| json!({ "testName": name, "message": "boom" });
+                match filter.as_deref() {
+                    Some("Game.Tests") => json!({
+                        "status": "completed", "testMode": "PlayMode", "runId": "r1",
+                        "totalTests": 5, "passedTests": 3, "failedTests": 2,
+                        "failures": [failure("Game.Tests.Flaky"), failure("Game.Tests.Broken")],
+                        "tests": [{ "fullName": "Game.Tests.Flaky", "status": "Failed" }]
+                    }),
+                    Some("Game.Tests.Flaky") => json!({
+                        "status": "completed", "runId": "r2",
+                        "totalTests": 1, "passedTests": 1, "failedTests": 0
+                    }),
+                    _ => json!({
+                        "status": "completed", "runId": "r3",
+                        "totalTests": 1, "passedTests": 0, "failedTests": 1,
+                        "failures": [failure("Game.Tests.Broken")]
+                    }),
+                }
+            }
+            _ => json!({}),
+        })
+        .await;
+
+        let status = run_with_reruns(&config(port), &options(), 2)
+            .await
+            .expect("run should complete");
+        // Flaky passes on attempt 2; Broken is retried on attempts 2 and 3.
+        assert_eq!(RERUNS.load(Ordering::SeqCst), 3);
+        assert_eq!(failed_count(&status), 1);
+        assert_eq!(status["passedTests"], 4);
+        assert_eq!(status["flaky"][0]["testName"], "Game.Tests.Flaky");
+        assert_eq!(status["flaky"][0]["passedOnAttempt"], 2);
+        assert_eq!(status["tests"][0]["status"], "Passed");
+        assert_eq!(
+            status["attempts"][2]["failed"],
+            json!(["Game.Tests.Broken"])
+        );
+        assert_eq!(status["runId"], "r1");
+        assert_eq!(status["lastRunId"], "r3");
+
+        let summary = render_summary(&status);
+        assert!(summary.contains("4 passed, 1 flaky, 1 failed"));
+        assert!(summary.contains("FLAKY Game.Tests.Flaky (passed on attempt 2)"));
+        assert!(summary.contains("FAILED Game.Tests.Broken"));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn rerun_errors_keep_the_first_run_and_count_as_failed() {
+        let (port, server) = spawn_editor(|request| match request["type"].as_str() {
+            Some("run_tests") if request["params"]["filter"] == "Game.Tests" => {
+                json!({ "status": "running" })
+            }
+            Some("run_tests") => json!({ "error": "Test runner crashed" }),
+            Some("get_test_status") => json!({
+                "status": "completed", "totalTests": 3, "passedTests": 2, "failedTests": 1,
+                "failures": [{ "testName": "Game.Tests.Crashy", "message": "boom" }]
+            }),
+            _ => json!({}),
+        })
+        .await;
+
+        let status = run_with_reruns(&config(port), &options(), 1)
+            .await
+            .expect("a failed rerun should not fail the run");
+        assert_eq!(failed_count(&status), 1);
+        assert_eq!(status["passedTests"], 2);
+        assert_eq!(status["failures"][0]["testName"], "Game.Tests.Crashy");
+        assert!(status["failures"][0]["rerunError"]
+            .as_str()
+            .is_some_and(|error| error.contains("Test runner crashed")));
+
+        server.abort();
+    }
+
+    #[test]
+    fn parse_mode_is_case_insensitive() {
+        assert_eq!(
+            parse_mode("playmode").expect("mode should parse"),
+            "PlayMode"
+        );
+        assert!(parse_mode("Runtime").is_err());
+    }
+}
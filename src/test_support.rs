@@ -0,0 +1,96 @@
+//! Mock Unity bridge shared by module tests.
+
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+use crate::config::RuntimeConfig;
+
+/// Fake bridge that answers every framed request on every connection with
+/// the envelope returned by `handler`; `Null` leaves the request unanswered.
+pub async fn spawn_bridge<F>(handler: F) -> (u16, JoinHandle<()>)
+where
+    F: Fn(&Value) -> Value + Send + Sync + Copy + 'static,
+{
+    let listener = TcpListener::bind(("127.0.0.1", 0))
+        .await
+        .expect("listener should bind");
+    let port = listener
+        .local_addr()
+        .expect("listener should have local addr")
+        .port();
+    let server = tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                loop {
+                    let mut len_buf = [0_u8; 4];
+                    if socket.read_exact(&mut len_buf).await.is_err() {
+                        break;
+                    }
+                    let mut payload = vec![0_u8; i32::from_be_bytes(len_buf) as usize];
+                    if socket.read_exact(&mut payload).await.is_err() {
+                        break;
+                    }
+                    let request: Value =
+                        serde_json::from_slice(&payload).expect("request should be JSON");
+                    let mut response = handler(&request);
+                    if response.is_null() {
+                        continue;
+                    }
+                    response["id"] = request["id"].clone();
+                    let bytes = serde_json::to_vec(&response).expect("response should serialize");
+                    let mut frame = (bytes.len() as i32).to_be_bytes().to_vec();
+                    frame.extend_from_slice(&bytes);
+                    if socket.write_all(&frame).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+    (port, server)
+}
+
+/// Like `spawn_bridge`, but `handler` returns the tool result and every reply
+/// is wrapped in a success envelope.
+pub async fn spawn_editor<F>(handler: F) -> (u16, JoinHandle<()>)
+where
+    F: Fn(&Value) -> Value + Send + Sync + Copy + 'static,
+{
+    spawn_bridge(move |request| success(handler(request))).await
+}
+
+pub fn success(result: Value) -> Value {
+    json!({ "status": "success", "result": result })
+}
+
+pub fn config(port: u16) -> RuntimeConfig {
+    RuntimeConfig {
+        host: "127.0.0.1".to_string(),
+        port,
+        timeout: Duration::from_millis(500),
+    }
+}
+
+/// Serializes tests that point `UNITY_CLI_REGISTRY_PATH` at a temporary
+/// registry, so they never touch the user's real one.
+pub fn registry_lock() -> &'static Mutex<()> {
+    static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+    LOCK.get_or_init(|| Mutex::new(()))
+}
+
+/// Unique registry path under the system temp dir.
+pub fn temp_registry_path(label: &str) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::from_secs(0))
+        .as_nanos();
+    let mut path = std::env::temp_dir();
+    path.push(format!("unity-cli-{label}-{nanos}.json"));
+    path
+}
@@ -1,125 +1,1126 @@
-pub const TOOL_NAMES: &[&str] = &[
-    "addressables_analyze",
-    "addressables_build",
-    "addressables_manage",
-    "get_animator_runtime_info",
-    "get_animator_state",
-    "find_by_component",
-    "get_component_values",
-    "get_gameobject_details",
-    "get_object_references",
-    "analyze_scene_contents",
-    "manage_asset_database",
-    "analyze_asset_dependencies",
-    "manage_asset_import_settings",
-    "create_material",
-    "modify_material",
-    "create_prefab",
-    "exit_prefab_mode",
-    "instantiate_prefab",
-    "modify_prefab",
-    "open_prefab",
-    "save_prefab",
-    "build_index",
-    "get_index_status",
-    "update_index",
-    "get_compilation_state",
-    "add_component",
-    "set_component_field",
-    "get_component_types",
-    "list_components",
-    "modify_component",
-    "remove_component",
-    "clear_console",
-    "read_console",
-    "manage_layers",
-    "quit_editor",
-    "manage_selection",
-    "manage_tags",
-    "manage_tools",
-    "manage_windows",
-    "create_gameobject",
-    "delete_gameobject",
-    "find_gameobject",
-    "get_hierarchy",
-    "modify_gameobject",
-    "add_input_action",
-    "create_action_map",
-    "remove_action_map",
-    "remove_input_action",
-    "analyze_input_actions_asset",
-    "get_input_actions_state",
-    "add_input_binding",
-    "create_composite_binding",
-    "remove_input_binding",
-    "remove_all_bindings",
-    "manage_control_schemes",
-    "input_gamepad",
-    "input_keyboard",
-    "input_mouse",
-    "input_system_control",
-    "input_touch",
-    "execute_menu_item",
-    "package_manager",
-    "registry_config",
-    "get_editor_state",
-    "pause_game",
-    "play_game",
-    "stop_game",
-    "playmode_wait_for_state",
-    "profiler_get_metrics",
-    "profiler_start",
-    "profiler_status",
-    "profiler_stop",
-    "create_scene",
-    "get_scene_info",
-    "list_scenes",
-    "load_scene",
-    "save_scene",
-    "analyze_screenshot",
-    "capture_screenshot",
-    "create_class",
-    "edit_snippet",
-    "edit_structured",
-    "list_packages",
-    "read",
-    "rename_symbol",
-    "find_refs",
-    "remove_symbol",
-    "search",
-    "find_symbol",
-    "get_symbols",
-    "search_tools",
-    "get_project_settings",
-    "update_project_settings",
-    "get_command_stats",
-    "get_server_info",
-    "ping",
-    "refresh_assets",
-    "get_test_status",
-    "run_tests",
-    "click_ui_element",
-    "find_ui_elements",
-    "get_ui_element_state",
-    "set_ui_element_value",
-    "simulate_ui_input",
-    "video_capture_for",
-    "capture_video_start",
-    "capture_video_status",
-    "capture_video_stop",
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ToolSpec {
+    pub name: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+    pub params: &'static [&'static str],
+}
+
+const fn tool(
+    name: &'static str,
+    category: &'static str,
+    description: &'static str,
+    params: &'static [&'static str],
+) -> ToolSpec {
+    ToolSpec {
+        name,
+        category,
+        description,
+        params,
+    }
+}
+
+pub const TOOL_SPECS: &[ToolSpec] = &[
+    tool(
+        "addressables_analyze",
+        "addressables",
+        "Analyze Addressables for duplicate, unused or dependent assets",
+        &["action", "assetPath", "groupName", "pageSize", "offset"],
+    ),
+    tool(
+        "addressables_build",
+        "addressables",
+        "Build or clean Addressables content for a target platform",
+        &["action", "buildTarget"],
+    ),
+    tool(
+        "addressables_manage",
+        "addressables",
+        "Manage Addressables groups, entries, addresses and labels",
+        &[
+            "action",
+            "assetPath",
+            "address",
+            "newAddress",
+            "groupName",
+            "targetGroupName",
+            "label",
+            "labels",
+            "pageSize",
+            "offset",
+        ],
+    ),
+    tool(
+        "get_animator_runtime_info",
+        "animator",
+        "Inspect runtime Animator data such as root motion, IK and playables",
+        &[
+            "gameObjectName",
+            "includeIK",
+            "includeRootMotion",
+            "includeBehaviours",
+        ],
+    ),
+    tool(
+        "get_animator_state",
+        "animator",
+        "Get current Animator layers, states, transitions and parameters",
+        &[
+            "gameObjectName",
+            "layerIndex",
+            "includeParameters",
+            "includeStates",
+            "includeTransitions",
+            "includeClips",
+        ],
+    ),
+    tool(
+        "find_by_component",
+        "analysis",
+        "Find GameObjects that have a given component type",
+        &[
+            "componentType",
+            "searchScope",
+            "includeInactive",
+            "matchExactType",
+        ],
+    ),
+    tool(
+        "get_component_values",
+        "analysis",
+        "Read serialized property values of a component on a GameObject",
+        &[
+            "gameObjectName",
+            "componentType",
+            "componentIndex",
+            "includePrivateFields",
+            "includeInherited",
+        ],
+    ),
+    tool(
+        "get_gameobject_details",
+        "analysis",
+        "Get detailed information about a GameObject, its components and children",
+        &[
+            "gameObjectName",
+            "path",
+            "includeChildren",
+            "includeComponents",
+            "includeMaterials",
+            "maxDepth",
+        ],
+    ),
+    tool(
+        "get_object_references",
+        "analysis",
+        "List objects referencing, or referenced by, a GameObject",
+        &[
+            "gameObjectName",
+            "includeAssetReferences",
+            "includeHierarchyReferences",
+            "searchInPrefabs",
+        ],
+    ),
+    tool(
+        "analyze_scene_contents",
+        "analysis",
+        "Summarize scene statistics, component distribution, rendering and memory usage",
+        &[
+            "includeInactive",
+            "groupByType",
+            "includePrefabInfo",
+            "includeMemoryInfo",
+        ],
+    ),
+    tool(
+        "manage_asset_database",
+        "asset",
+        "Find, inspect, create, move, copy or delete assets and folders",
+        &[
+            "action",
+            "filter",
+            "searchInFolders",
+            "assetPath",
+            "folderPath",
+            "fromPath",
+            "toPath",
+        ],
+    ),
+    tool(
+        "analyze_asset_dependencies",
+        "asset",
+        "Analyze asset dependencies, dependents, circular references and unused assets",
+        &["action", "assetPath", "recursive", "includeBuiltIn"],
+    ),
+    tool(
+        "manage_asset_import_settings",
+        "asset",
+        "Get or modify asset importer settings, apply presets and reimport",
+        &["action", "assetPath", "settings", "preset"],
+    ),
+    tool(
+        "create_material",
+        "asset",
+        "Create a material asset with a shader and initial properties",
+        &[
+            "materialPath",
+            "shader",
+            "properties",
+            "copyFrom",
+            "overwrite",
+        ],
+    ),
+    tool(
+        "modify_material",
+        "asset",
+        "Change shader or property values of an existing material",
+        &["materialPath", "properties", "shader"],
+    ),
+    tool(
+        "create_prefab",
+        "prefab",
+        "Create a prefab asset from a scene GameObject or template",
+        &[
+            "gameObjectPath",
+            "prefabPath",
+            "createFromTemplate",
+            "overwrite",
+        ],
+    ),
+    tool(
+        "exit_prefab_mode",
+        "prefab",
+        "Leave prefab editing mode, optionally saving changes",
+        &["saveChanges"],
+    ),
+    tool(
+        "instantiate_prefab",
+        "prefab",
+        "Instantiate a prefab into the open scene",
+        &["prefabPath", "position", "rotation", "parent", "name"],
+    ),
+    tool(
+        "modify_prefab",
+        "prefab",
+        "Apply property modifications to a prefab asset",
+        &["prefabPath", "modifications", "applyToInstances"],
+    ),
+    tool(
+        "open_prefab",
+        "prefab",
+        "Open a prefab asset in prefab editing mode",
+        &["prefabPath", "focusObject", "isolateObject"],
+    ),
+    tool(
+        "save_prefab",
+        "prefab",
+        "Save the prefab currently being edited or a prefab instance",
+        &["gameObjectPath", "includeChildren"],
+    ),
+    tool(
+        "build_index",
+        "code_index",
+        "Build the local C# symbol index for the project",
+        &["scope", "excludePackageCache", "outputPath"],
+    ),
+    tool(
+        "get_index_status",
+        "code_index",
+        "Report whether the C# symbol index is built and up to date",
+        &[],
+    ),
+    tool(
+        "update_index",
+        "code_index",
+        "Refresh symbol index entries for specific C# files",
+        &["paths"],
+    ),
+    tool(
+        "get_compilation_state",
+        "compilation",
+        "Report script compilation status and compiler messages",
+        &["includeMessages", "maxMessages"],
+    ),
+    tool(
+        "add_component",
+        "component",
+        "Add a component to a GameObject",
+        &["gameObjectPath", "componentType", "properties"],
+    ),
+    tool(
+        "set_component_field",
+        "component",
+        "Set a serialized field on a component in a scene or prefab",
+        &[
+            "gameObjectPath",
+            "componentType",
+            "componentIndex",
+            "fieldPath",
+            "value",
+            "valueType",
+            "enumValue",
+            "objectReference",
+            "scope",
+            "prefabAssetPath",
+            "prefabObjectPath",
+            "serializedPropertyPath",
+            "dryRun",
+            "applyPrefabChanges",
+            "createUndo",
+            "markSceneDirty",
+            "runtime",
+        ],
+    ),
+    tool(
+        "get_component_types",
+        "component",
+        "List available component types, optionally filtered by category or name",
+        &["category", "search", "onlyAddable"],
+    ),
+    tool(
+        "list_components",
+        "component",
+        "List components attached to a GameObject",
+        &["gameObjectPath", "includeProperties"],
+    ),
+    tool(
+        "modify_component",
+        "component",
+        "Modify property values of a component on a GameObject",
+        &[
+            "gameObjectPath",
+            "componentType",
+            "componentIndex",
+            "properties",
+        ],
+    ),
+    tool(
+        "remove_component",
+        "component",
+        "Remove a component from a GameObject",
+        &["gameObjectPath", "componentType", "componentIndex"],
+    ),
+    tool(
+        "clear_console",
+        "console",
+        "Clear Unity console logs, optionally preserving warnings or errors",
+        &[
+            "clearOnPlay",
+            "clearOnRecompile",
+            "clearOnBuild",
+            "preserveWarnings",
+            "preserveErrors",
+        ],
+    ),
+    tool(
+        "read_console",
+        "console",
+        "Read Unity console logs filtered by type, text or timestamp",
+        &[
+            "count",
+            "logTypes",
+            "filterText",
+            "includeStackTrace",
+            "format",
+            "sinceTimestamp",
+            "untilTimestamp",
+            "sortOrder",
+            "groupBy",
+        ],
+    ),
+    tool(
+        "manage_layers",
+        "editor",
+        "List, add or remove layers",
+        &["action", "layerName", "layerIndex"],
+    ),
+    tool("quit_editor", "editor", "Quit the Unity Editor", &[]),
+    tool(
+        "manage_selection",
+        "editor",
+        "Get, set or clear the editor selection",
+        &["action", "objectPaths", "includeDetails"],
+    ),
+    tool(
+        "manage_tags",
+        "editor",
+        "List, add or remove tags",
+        &["action", "tagName"],
+    ),
+    tool(
+        "manage_tools",
+        "editor",
+        "List, activate or deactivate editor tools and packages",
+        &["action", "category", "toolName"],
+    ),
+    tool(
+        "manage_windows",
+        "editor",
+        "List, focus or inspect editor windows",
+        &["action", "windowType", "includeHidden"],
+    ),
+    tool(
+        "create_gameobject",
+        "gameobject",
+        "Create a GameObject or primitive in the scene",
+        &[
+            "name",
+            "primitiveType",
+            "position",
+            "rotation",
+            "scale",
+            "parentPath",
+            "tag",
+            "layer",
+        ],
+    ),
+    tool(
+        "delete_gameobject",
+        "gameobject",
+        "Delete one or more GameObjects by path",
+        &["path", "paths", "includeChildren"],
+    ),
+    tool(
+        "find_gameobject",
+        "gameobject",
+        "Find GameObjects by name, tag or layer",
+        &["name", "tag", "layer", "exactMatch"],
+    ),
+    tool(
+        "get_hierarchy",
+        "gameobject",
+        "Get the scene hierarchy tree",
+        &[
+            "rootPath",
+            "includeInactive",
+            "maxDepth",
+            "includeComponents",
+            "includeTransform",
+            "includeTags",
+            "includeLayers",
+            "nameOnly",
+            "maxObjects",
+        ],
+    ),
+    tool(
+        "modify_gameobject",
+        "gameobject",
+        "Change name, transform, active state, tag, layer or parent of a GameObject",
+        &[
+            "path",
+            "name",
+            "position",
+            "rotation",
+            "scale",
+            "active",
+            "tag",
+            "layer",
+            "parentPath",
+            "runtime",
+        ],
+    ),
+    tool(
+        "add_input_action",
+        "input_actions",
+        "Add an action to an Input Actions asset map",
+        &["assetPath", "mapName", "actionName", "actionType"],
+    ),
+    tool(
+        "create_action_map",
+        "input_actions",
+        "Create an action map in an Input Actions asset",
+        &["assetPath", "mapName", "actions"],
+    ),
+    tool(
+        "remove_action_map",
+        "input_actions",
+        "Remove an action map from an Input Actions asset",
+        &["assetPath", "mapName"],
+    ),
+    tool(
+        "remove_input_action",
+        "input_actions",
+        "Remove an action from an Input Actions asset map",
+        &["assetPath", "mapName", "actionName"],
+    ),
+    tool(
+        "analyze_input_actions_asset",
+        "input_actions",
+        "Analyze an Input Actions asset and report maps, actions and bindings",
+        &["assetPath", "includeJsonStructure", "includeStatistics"],
+    ),
+    tool(
+        "get_input_actions_state",
+        "input_actions",
+        "Get action maps, bindings and control schemes of an Input Actions asset",
+        &[
+            "assetName",
+            "assetPath",
+            "includeBindings",
+            "includeControlSchemes",
+            "includeJsonStructure",
+        ],
+    ),
+    tool(
+        "add_input_binding",
+        "input_actions",
+        "Add a binding to an input action",
+        &[
+            "assetPath",
+            "mapName",
+            "actionName",
+            "path",
+            "groups",
+            "interactions",
+            "processors",
+        ],
+    ),
+    tool(
+        "create_composite_binding",
+        "input_actions",
+        "Create a composite binding such as 2D vector WASD controls",
+        &[
+            "assetPath",
+            "mapName",
+            "actionName",
+            "compositeType",
+            "name",
+            "bindings",
+            "groups",
+        ],
+    ),
+    tool(
+        "remove_input_binding",
+        "input_actions",
+        "Remove a binding from an input action by index or path",
+        &[
+            "assetPath",
+            "mapName",
+            "actionName",
+            "bindingIndex",
+            "bindingPath",
+        ],
+    ),
+    tool(
+        "remove_all_bindings",
+        "input_actions",
+        "Remove every binding from an input action",
+        &["assetPath", "mapName", "actionName"],
+    ),
+    tool(
+        "manage_control_schemes",
+        "input_actions",
+        "Add, modify or remove control schemes of an Input Actions asset",
+        &["assetPath", "operation", "schemeName", "devices"],
+    ),
+    tool(
+        "input_gamepad",
+        "input",
+        "Simulate gamepad buttons, sticks and triggers",
+        &[
+            "action",
+            "button",
+            "buttonAction",
+            "stick",
+            "x",
+            "y",
+            "trigger",
+            "value",
+            "direction",
+        ],
+    ),
+    tool(
+        "input_keyboard",
+        "input",
+        "Simulate keyboard key presses and text typing",
+        &["action", "key", "keys", "text", "holdSeconds"],
+    ),
+    tool(
+        "input_mouse",
+        "input",
+        "Simulate mouse movement, clicks, drags and scrolling",
+        &[
+            "action",
+            "x",
+            "y",
+            "deltaX",
+            "deltaY",
+            "button",
+            "buttonAction",
+            "clickCount",
+            "absolute",
+        ],
+    ),
+    tool(
+        "input_system_control",
+        "input",
+        "Run input sequences and read the current simulated input state",
+        &["operation", "sequence", "delayBetween"],
+    ),
+    tool(
+        "input_touch",
+        "input",
+        "Simulate touch taps, swipes and pinch gestures",
+        &[
+            "action", "touchId", "x", "y", "phase", "startX", "startY", "endX", "endY", "duration",
+            "touches",
+        ],
+    ),
+    tool(
+        "execute_menu_item",
+        "menu",
+        "Execute or list Unity editor menu items",
+        &["action", "menuPath", "alias", "safetyCheck", "parameters"],
+    ),
+    tool(
+        "package_manager",
+        "package",
+        "Search, list, install, remove or inspect Unity packages",
+        &[
+            "action",
+            "packageName",
+            "packageId",
+            "version",
+            "keyword",
+            "limit",
+            "includeBuiltIn",
+        ],
+    ),
+    tool(
+        "registry_config",
+        "package",
+        "Configure scoped registries such as OpenUPM and NuGet",
+        &[
+            "action",
+            "registryName",
+            "url",
+            "scope",
+            "scopes",
+            "autoAddPopular",
+        ],
+    ),
+    tool(
+        "get_editor_state",
+        "playmode",
+        "Get editor play, pause and compile state",
+        &[],
+    ),
+    tool("pause_game", "playmode", "Pause or resume play mode", &[]),
+    tool("play_game", "playmode", "Enter play mode", &[]),
+    tool("stop_game", "playmode", "Exit play mode", &[]),
+    tool(
+        "playmode_wait_for_state",
+        "playmode",
+        "Wait until the editor reaches a play mode state",
+        &["isPlaying", "timeoutMs", "pollMs"],
+    ),
+    tool(
+        "profiler_get_metrics",
+        "profiler",
+        "Read profiler metrics or list available metrics",
+        &["listAvailable", "metrics"],
+    ),
+    tool(
+        "profiler_start",
+        "profiler",
+        "Start a profiler session, optionally recording to a file",
+        &["mode", "recordToFile", "metrics", "maxDurationSec"],
+    ),
+    tool(
+        "profiler_status",
+        "profiler",
+        "Report the state of the current profiler session",
+        &[],
+    ),
+    tool(
+        "profiler_stop",
+        "profiler",
+        "Stop a profiler session and return its output",
+        &["sessionId"],
+    ),
+    tool(
+        "create_scene",
+        "scene",
+        "Create a new scene, optionally loading it and adding it to build settings",
+        &["sceneName", "path", "loadScene", "addToBuildSettings"],
+    ),
+    tool(
+        "get_scene_info",
+        "scene",
+        "Get information about a scene and its root GameObjects",
+        &["scenePath", "sceneName", "includeGameObjects"],
+    ),
+    tool(
+        "list_scenes",
+        "scene",
+        "List scenes in the project, build settings or currently loaded",
+        &["includeLoadedOnly", "includeBuildScenesOnly", "includePath"],
+    ),
+    tool(
+        "load_scene",
+        "scene",
+        "Open a scene in single or additive mode",
+        &["scenePath", "sceneName", "loadMode"],
+    ),
+    tool(
+        "save_scene",
+        "scene",
+        "Save the active scene, optionally to a new path",
+        &["scenePath", "saveAs"],
+    ),
+    tool(
+        "analyze_screenshot",
+        "screenshot",
+        "Analyze a captured screenshot image",
+        &["imagePath", "analysisType"],
+    ),
+    tool(
+        "capture_screenshot",
+        "screenshot",
+        "Capture a screenshot of the game view, scene view or an editor window",
+        &[
+            "captureMode",
+            "width",
+            "height",
+            "includeUI",
+            "windowName",
+            "encodeAsBase64",
+            "workspaceRoot",
+        ],
+    ),
+    tool(
+        "create_class",
+        "script",
+        "Create a new C# class file",
+        &["path", "className", "namespace", "baseType", "usings"],
+    ),
+    tool(
+        "edit_snippet",
+        "script",
+        "Apply small anchored text edits to a C# file",
+        &["path", "instructions", "preview"],
+    ),
+    tool(
+        "edit_structured",
+        "script",
+        "Replace, insert or delete C# symbols structurally",
+        &["path", "symbolName", "operation", "newText"],
+    ),
+    tool(
+        "list_packages",
+        "script",
+        "List packages under the project Packages directory",
+        &[],
+    ),
+    tool(
+        "read",
+        "script",
+        "Read a range of lines from a project file",
+        &["path", "startLine", "maxLines"],
+    ),
+    tool(
+        "rename_symbol",
+        "script",
+        "Rename a C# symbol across the project",
+        &["relative", "namePath", "newName", "preview"],
+    ),
+    tool(
+        "find_refs",
+        "script",
+        "Find references to a C# symbol name",
+        &[
+            "name",
+            "scope",
+            "path",
+            "startAfter",
+            "pageSize",
+            "maxBytes",
+            "maxMatchesPerFile",
+            "snippetContext",
+        ],
+    ),
+    tool(
+        "remove_symbol",
+        "script",
+        "Remove a C# symbol declaration",
+        &["path", "namePath", "preview"],
+    ),
+    tool(
+        "search",
+        "script",
+        "Search C# source files with a regular expression",
+        &["pattern", "path", "limit"],
+    ),
+    tool(
+        "find_symbol",
+        "script",
+        "Find C# symbol definitions by name in the symbol index",
+        &["name", "kind", "scope", "exact"],
+    ),
+    tool(
+        "get_symbols",
+        "script",
+        "List classes, methods, properties and fields declared in a C# file",
+        &["path"],
+    ),
+    tool(
+        "search_tools",
+        "system",
+        "Search the tool catalog by keyword",
+        &["query", "category", "limit"],
+    ),
+    tool(
+        "get_project_settings",
+        "settings",
+        "Read player, graphics, quality, physics, audio and other project settings",
+        &[
+            "includePlayer",
+            "includeGraphics",
+            "includeQuality",
+            "includePhysics",
+            "includePhysics2D",
+            "includeAudio",
+            "includeTime",
+            "includeInputManager",
+            "includeEditor",
+            "includeBuild",
+            "includeTags",
+        ],
+    ),
+    tool(
+        "update_project_settings",
+        "settings",
+        "Update project settings sections after confirmation",
+        &[
+            "confirmChanges",
+            "player",
+            "graphics",
+            "physics",
+            "audio",
+            "time",
+        ],
+    ),
+    tool(
+        "get_command_stats",
+        "system",
+        "Report command counts and recent commands handled by the bridge",
+        &[],
+    ),
+    tool(
+        "get_server_info",
+        "system",
+        "Report CLI, bridge and project information for diagnostics",
+        &[],
+    ),
+    tool(
+        "ping",
+        "system",
+        "Check connectivity with the Unity bridge",
+        &["message"],
+    ),
+    tool(
+        "refresh_assets",
+        "system",
+        "Refresh the asset database and trigger recompilation",
+        &[],
+    ),
+    tool(
+        "get_test_status",
+        "test",
+        "Get the status and results of the current or latest test run",
+        &["includeTestResults", "includeFileContent"],
+    ),
+    tool(
+        "run_tests",
+        "test",
+        "Run EditMode or PlayMode tests with optional filters",
+        &[
+            "testMode",
+            "filter",
+            "category",
+            "namespace",
+            "includeDetails",
+            "exportPath",
+        ],
+    ),
+    tool(
+        "click_ui_element",
+        "ui",
+        "Click a UI element such as a button or toggle",
+        &["elementPath", "clickType", "holdDuration", "position"],
+    ),
+    tool(
+        "find_ui_elements",
+        "ui",
+        "Find UI elements by type, name pattern, tag or canvas",
+        &[
+            "elementType",
+            "namePattern",
+            "tagFilter",
+            "canvasFilter",
+            "uiDocumentFilter",
+            "uiSystem",
+            "includeInactive",
+        ],
+    ),
+    tool(
+        "get_ui_element_state",
+        "ui",
+        "Get the state and interactability of a UI element",
+        &["elementPath", "includeChildren", "includeInteractableInfo"],
+    ),
+    tool(
+        "set_ui_element_value",
+        "ui",
+        "Set the value of a UI element such as a slider, toggle, input field or dropdown",
+        &["elementPath", "value", "triggerEvents"],
+    ),
+    tool(
+        "simulate_ui_input",
+        "ui",
+        "Run a sequence of UI interactions and validate the resulting state",
+        &["inputSequence", "waitBetween", "validateState"],
+    ),
+    tool(
+        "video_capture_for",
+        "video",
+        "Record the game view for a fixed duration, optionally while playing",
+        &["durationSec", "play", "fps", "width", "height", "format"],
+    ),
+    tool(
+        "capture_video_start",
+        "video",
+        "Start recording the game view to a video file",
+        &[
+            "captureMode",
+            "width",
+            "height",
+            "fps",
+            "includeUI",
+            "maxDurationSec",
+            "format",
+            "workspaceRoot",
+        ],
+    ),
+    tool(
+        "capture_video_status",
+        "video",
+        "Report the state of the current video recording",
+        &[],
+    ),
+    tool(
+        "capture_video_stop",
+        "video",
+        "Stop the current video recording",
+        &[],
+    ),
+];
+
+const NAME_WEIGHT: f64 = 3.0;
+const CATEGORY_WEIGHT: f64 = 2.0;
+const PARAM_WEIGHT: f64 = 1.5;
+const DESCRIPTION_WEIGHT: f64 = 1.0;
+
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "the", "which", "what", "how", "tool", "tools", "to", "of", "for", "in",
+    "on", "is", "do", "does", "that", "with", "i", "can", "me",
 ];
 
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolMatch {
+    #[serde(flatten)]
+    pub spec: ToolSpec,
+    pub score: f64,
+}
+
+pub fn tool_names() -> impl Iterator<Item = &'static str> {
+    TOOL_SPECS.iter().map(|spec| spec.name)
+}
+
 pub fn is_known_tool(name: &str) -> bool {
-    TOOL_NAMES.contains(&name)
+    find_tool(name).is_some()
+}
+
+pub fn find_tool(name: &str) -> Option<&'static ToolSpec> {
+    TOOL_SPECS.iter().find(|spec| spec.name == name)
+}
+
+/// Ranks catalog entries against a free-text query using keyword and fuzzy
+/// matching over names, categories, parameter names and descriptions.
+pub fn search_tools(query: &str, category: Option<&str>) -> Vec<ToolMatch> {
+    let terms = query_terms(query);
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    let compact_query = terms.join("_");
+    let max_score = NAME_WEIGHT * terms.len() as f64;
+    let mut matches = Vec::new();
+
+    for spec in TOOL_SPECS {
+        if let Some(expected) = category {
+            if !spec.category.eq_ignore_ascii_case(expected) {
+                continue;
+            }
+        }
+
+        let name_tokens = split_words(spec.name);
+        let category_tokens = split_words(spec.category);
+        let param_tokens = spec
+            .params
+            .iter()
+            .flat_map(|param| split_words(param))
+            .collect::<Vec<_>>();
+        let description_tokens = split_words(spec.description);
+
+        let mut total = 0.0;
+        for term in &terms {
+            let best = [
+                (NAME_WEIGHT, &name_tokens),
+                (CATEGORY_WEIGHT, &category_tokens),
+                (PARAM_WEIGHT, &param_tokens),
+                (DESCRIPTION_WEIGHT, &description_tokens),
+            ]
+            .iter()
+            .map(|(weight, tokens)| weight * best_token_match(term, tokens))
+            .fold(0.0, f64::max);
+            total += best;
+        }
+
+        if total <= 0.0 {
+            continue;
+        }
+
+        let mut score = total / max_score;
+        if spec.name == compact_query {
+            score += 0.5;
+        } else if spec.name.contains(&compact_query) {
+            score += 0.25;
+        }
+
+        matches.push(ToolMatch {
+            spec: *spec,
+            score: (score.min(1.0) * 1000.0).round() / 1000.0,
+        });
+    }
+
+    matches.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.spec.name.cmp(b.spec.name))
+    });
+    matches
+}
+
+fn query_terms(query: &str) -> Vec<String> {
+    split_words(query)
+        .into_iter()
+        .filter(|word| !STOP_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// Splits snake_case, camelCase and free text into lowercase, singularized words.
+fn split_words(raw: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+
+    for ch in raw.chars() {
+        if !ch.is_alphanumeric() {
+            push_word(&mut words, &mut current);
+            previous_lower = false;
+            continue;
+        }
+        if ch.is_uppercase() && previous_lower {
+            push_word(&mut words, &mut current);
+        }
+        previous_lower = ch.is_lowercase() || ch.is_ascii_digit();
+        current.extend(ch.to_lowercase());
+    }
+    push_word(&mut words, &mut current);
+    words
+}
+
+fn push_word(words: &mut Vec<String>, current: &mut String) {
+    if current.is_empty() {
+        return;
+    }
+    let word = std::mem::take(current);
+    words.push(singularize(&word));
+}
+
+fn singularize(word: &str) -> String {
+    if word.len() > 3 && word.ends_with('s') && !word.ends_with("ss") {
+        word[..word.len() - 1].to_string()
+    } else {
+        word.to_string()
+    }
+}
+
+fn best_token_match(term: &str, tokens: &[String]) -> f64 {
+    tokens
+        .iter()
+        .map(|token| token_match(term, token))
+        .fold(0.0, f64::max)
+}
+
+fn token_match(term: &str, token: &str) -> f64 {
+    if term == token {
+        return 1.0;
+    }
+    let shorter = term.len().min(token.len());
+    if shorter >= 3 && (token.starts_with(term) || term.starts_with(token)) {
+        return 0.7;
+    }
+    if term.len() >= 4 && token.len() >= 4 {
+        let distance = levenshtein(term, token);
+        let similarity = 1.0 - distance as f64 / term.len().max(token.len()) as f64;
+        if similarity >= 0.75 {
+            return similarity * 0.6;
+        }
+    }
+    0.0
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b_chars = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b_chars.len()).collect::<Vec<_>>();
+    let mut current = vec![0; b_chars.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b_chars.iter().enumerate() {
+            let cost = usize::from(a_char != *b_char);
+            current[j + 1] = (previous[j + 1] + 1)
+                .min(current[j] + 1)
+                .min(previous[j] + cost);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b_chars.len()]
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{is_known_tool, TOOL_NAMES};
+    use super::{is_known_tool, search_tools, split_words, TOOL_SPECS};
+    use std::collections::BTreeSet;
 
     #[test]
     fn tool_catalog_keeps_manifest_parity_count() {
-        assert_eq!(TOOL_NAMES.len(), 108);
+        assert_eq!(TOOL_SPECS.len(), 108);
+    }
+
+    #[test]
+    fn tool_catalog_names_are_unique() {
+        let names = TOOL_SPECS
+            .iter()
+            .map(|spec| spec.name)
+            .collect::<BTreeSet<_>>();
+        assert_eq!(names.len(), TOOL_SPECS.len());
     }
 
     #[test]
@@ -127,4 +1128,33 @@ mod tests {
         assert!(is_known_tool("ping"));
         assert!(!is_known_tool("not_existing_tool"));
     }
+
+    #[test]
+    fn split_words_handles_snake_and_camel_case() {
+        assert_eq!(
+            split_words("set_ui_element_value"),
+            vec!["set", "ui", "element", "value"]
+        );
+        assert_eq!(
+            split_words("gameObjectPath"),
+            vec!["game", "object", "path"]
+        );
+    }
+
+    #[test]
+    fn search_tools_ranks_natural_language_query() {
+        let results = search_tools("which tool sets a UI slider value?", None);
+        assert_eq!(results[0].spec.name, "set_ui_element_value");
+        assert!(results[0].score > results[1].score);
+    }
+
+    #[test]
+    fn search_tools_tolerates_typos_and_filters_by_category() {
+        let results = search_tools("screenshoot", None);
+        assert_eq!(results[0].spec.category, "screenshot");
+
+        let results = search_tools("capture", Some("video"));
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|item| item.spec.category == "video"));
+    }
 }
@@ -36,6 +36,13 @@ impl UnityClient {
     }
 
     pub async fn call_tool(&mut self, tool_name: &str, params: Value) -> Result<Value> {
+        let response = self.call_tool_raw(tool_name, params).await?;
+        normalize_response(response)
+    }
+
+    /// Sends a command and returns the response envelope as-is, including
+    /// bridge metadata such as `editorState`.
+    pub async fn call_tool_raw(&mut self, tool_name: &str, params: Value) -> Result<Value> {
         if !params.is_object() {
             bail!("Tool parameters must be a JSON object");
         }
@@ -48,8 +55,7 @@ impl UnityClient {
         self.next_id += 1;
 
         self.send_framed(&request).await?;
-        let response = self.read_response().await?;
-        normalize_response(response)
+        self.read_response().await
     }
 
     async fn send_framed(&mut self, request: &Value) -> Result<()> {
@@ -125,7 +131,9 @@ fn parse_json(bytes: &[u8]) -> Result<Value> {
     serde_json::from_str(trimmed).context("Unity response was not valid JSON")
 }
 
-fn normalize_response(response: Value) -> Result<Value> {
+/// Extracts the result from a response envelope, turning error envelopes into
+/// errors.
+pub fn normalize_response(response: Value) -> Result<Value> {
     let status = response
         .get("status")
         .and_then(Value::as_str)
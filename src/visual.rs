@@ -0,0 +1,652 @@
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use walkdir::WalkDir;
+
+/// Largest possible YIQ delta between two colours (black vs. white).
+const MAX_YIQ_DELTA: f64 = 35215.0;
+
+#[derive(Debug, Clone)]
+pub struct CompareOptions {
+    pub baseline: PathBuf,
+    pub current: PathBuf,
+    /// Largest tolerated share of differing pixels, as a fraction (0.005 = 0.5%).
+    pub threshold: f64,
+    /// Per-pixel perceptual sensitivity in `0..=1`; lower is stricter.
+    pub pixel_threshold: f64,
+    pub mask: Mask,
+    /// Report directory; diff images go to `diff/` inside it.
+    pub out: PathBuf,
+}
+
+/// Decoded image as 8-bit RGBA.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Region {
+    fn contains(&self, x: u32, y: u32) -> bool {
+        x >= self.x
+            && y >= self.y
+            && u64::from(x) < u64::from(self.x) + u64::from(self.width)
+            && u64::from(y) < u64::from(self.y) + u64::from(self.height)
+    }
+}
+
+/// Ignore regions: `regions` apply to every image, `files` to the image with
+/// that relative path. A bare array is shorthand for `regions`.
+#[derive(Debug, Clone, Default)]
+pub struct Mask {
+    regions: Vec<Region>,
+    files: Vec<(String, Vec<Region>)>,
+}
+
+impl Mask {
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read mask: {}", path.display()))?;
+        let value: Value = serde_json::from_str(&raw)
+            .with_context(|| format!("Invalid JSON in mask: {}", path.display()))?;
+        Self::from_value(&value).with_context(|| format!("Invalid mask: {}", path.display()))
+    }
+
+    fn from_value(value: &Value) -> Result<Self> {
+        if value.is_array() {
+            return Ok(Self {
+                regions: parse_regions(value)?,
+                files: Vec::new(),
+            });
+        }
+        let Some(object) = value.as_object() else {
+            bail!("expected an object or an array of regions");
+        };
+        let regions = match object.get("regions") {
+            Some(regions) => parse_regions(regions)?,
+            None => Vec::new(),
+        };
+        let mut files = Vec::new();
+        if let Some(entries) = object.get("files") {
+            let Some(entries) = entries.as_object() else {
+                bail!("`files` must map image paths to region arrays");
+            };
+            for (name, regions) in entries {
+                files.push((name.clone(), parse_regions(regions)?));
+            }
+        }
+        Ok(Self { regions, files })
+    }
+
+    fn regions_for(&self, name: &str) -> Vec<Region> {
+        let mut regions = self.regions.clone();
+        for (file, extra) in &self.files {
+            if file == name {
+                regions.extend_from_slice(extra);
+            }
+        }
+        regions
+    }
+}
+
+fn parse_regions(value: &Value) -> Result<Vec<Region>> {
+    let Some(items) = value.as_array() else {
+        bail!("regions must be an array");
+    };
+    items
+        .iter()
+        .map(|item| {
+            let field = |key: &str| {
+                item.get(key)
+                    .and_then(Value::as_u64)
+                    .and_then(|value| u32::try_from(value).ok())
+                    .with_context(|| format!("region {item} needs a non-negative integer `{key}`"))
+            };
+            Ok(Region {
+                x: field("x")?,
+                y: field("y")?,
+                width: field("width")?,
+                height: field("height")?,
+            })
+        })
+        .collect()
+}
+
+/// Parses `0.5%` or `0.5` (both percent) into a fraction.
+pub fn parse_threshold(raw: &str) -> Result<f64> {
+    let trimmed = raw.trim();
+    let number = trimmed.strip_suffix('%').unwrap_or(trimmed).trim();
+    match number.parse::<f64>() {
+        Ok(percent) if (0.0..=100.0).contains(&percent) => Ok(percent / 100.0),
+        _ => bail!("Invalid --threshold `{raw}`; expected a percentage such as 0.5%"),
+    }
+}
+
+/// Result of comparing two images of the same size.
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub different: u64,
+    pub compared: u64,
+    pub masked: u64,
+    pub mean_delta: f64,
+    pub max_delta: f64,
+    /// Faded baseline with differing pixels in red and masked ones in blue.
+    pub diff: Image,
+}
+
+impl Comparison {
+    pub fn ratio(&self) -> f64 {
+        if self.compared == 0 {
+            0.0
+        } else {
+            self.different as f64 / self.compared as f64
+        }
+    }
+}
+
+/// Compares two same-sized images. A pixel differs when its YIQ distance
+/// exceeds `pixel_threshold`; `mean_delta`/`max_delta` are perceptual scores
+/// in `0..=1`.
+pub fn compare_images(
+    baseline: &Image,
+    current: &Image,
+    regions: &[Region],
+    pixel_threshold: f64,
+) -> Comparison {
+    let limit = MAX_YIQ_DELTA * pixel_threshold * pixel_threshold;
+    let mut diff = Vec::with_capacity(baseline.rgba.len());
+    let (mut different, mut compared, mut masked) = (0u64, 0u64, 0u64);
+    let (mut total_delta, mut max_delta) = (0.0f64, 0.0f64);
+
+    for (index, (before, after)) in baseline
+        .rgba
+        .chunks_exact(4)
+        .zip(current.rgba.chunks_exact(4))
+        .enumerate()
+    {
+        let x = index as u32 % baseline.width;
+        let y = index as u32 / baseline.width;
+        if regions.iter().any(|region| region.contains(x, y)) {
+            masked += 1;
+            diff.extend_from_slice(&[64, 96, 255, 255]);
+            continue;
+        }
+        compared += 1;
+        let delta = yiq_delta(before, after);
+        let score = (delta / MAX_YIQ_DELTA).sqrt();
+        total_delta += score;
+        max_delta = max_delta.max(score);
+        if delta > limit {
+            different += 1;
+            diff.extend_from_slice(&[255, 0, 0, 255]);
+        } else {
+            let gray = 255 - (255 - luma(before)) / 4;
+            diff.extend_from_slice(&[gray, gray, gray, 255]);
+        }
+    }
+
+    Comparison {
+        different,
+        compared,
+        masked,
+        mean_delta: if compared == 0 {
+            0.0
+        } else {
+            total_delta / compared as f64
+        },
+        max_delta,
+        diff: Image {
+            width: baseline.width,
+            height: baseline.height,
+            rgba: diff,
+        },
+    }
+}
+
+/// Squared YIQ distance after blending both pixels onto white, as in
+/// pixelmatch.
+fn yiq_delta(a: &[u8], b: &[u8]) -> f64 {
+    let (y1, i1, q1) = yiq(a);
+    let (y2, i2, q2) = yiq(b);
+    let (dy, di, dq) = (y1 - y2, i1 - i2, q1 - q2);
+    0.5053 * dy * dy + 0.299 * di * di + 0.1957 * dq * dq
+}
+
+fn yiq(pixel: &[u8]) -> (f64, f64, f64) {
+    let alpha = f64::from(pixel[3]) / 255.0;
+    let blend = |channel: u8| 255.0 + (f64::from(channel) - 255.0) * alpha;
+    let (r, g, b) = (blend(pixel[0]), blend(pixel[1]), blend(pixel[2]));
+    (
+        r * 0.298_895_31 + g * 0.586_622_47 + b * 0.114_482_23,
+        r * 0.595_977_99 - g * 0.274_176_10 - b * 0.321_801_89,
+        r * 0.211_470_17 - g * 0.522_617_11 + b * 0.311_146_94,
+    )
+}
+
+fn luma(pixel: &[u8]) -> u8 {
+    yiq(pixel).0.round().clamp(0.0, 255.0) as u8
+}
+
+pub fn decode_png(path: &Path) -> Result<Image> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut decoder = png::Decoder::new(file);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder
+        .read_info()
+        .with_context(|| format!("Failed to decode PNG {}", path.display()))?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader
+        .next_frame(&mut buffer)
+        .with_context(|| format!("Failed to decode PNG {}", path.display()))?;
+    buffer.truncate(info.buffer_size());
+
+    let rgba = match info.color_type {
+        png::ColorType::Rgba => buffer,
+        png::ColorType::Rgb => buffer
+            .chunks_exact(3)
+            .flat_map(|px| [px[0], px[1], px[2], 255])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => buffer
+            .chunks_exact(2)
+            .flat_map(|px| [px[0], px[0], px[0], px[1]])
+            .collect(),
+        png::ColorType::Grayscale => buffer.iter().flat_map(|&v| [v, v, v, 255]).collect(),
+        png::ColorType::Indexed => bail!("Unexpanded palette PNG {}", path.display()),
+    };
+    Ok(Image {
+        width: info.width,
+        height: info.height,
+        rgba,
+    })
+}
+
+pub fn encode_png(path: &Path, image: &Image) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let file =
+        File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), image.width, image.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder
+        .write_header()
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    writer
+        .write_image_data(&image.rgba)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// Compares every PNG under `baseline` with the file at the same relative
+/// path under `current`, writes diff images plus `report.json` and
+/// `report.html` to `out`, and returns the report. `passed` is false when an
+/// image is above the threshold, missing, differently sized or undecodable.
+pub fn compare_dirs(options: &CompareOptions) -> Result<Value> {
+    let baseline_files = png_files(&options.baseline)?;
+    let current_files = png_files(&options.current)?;
+    if baseline_files.is_empty() {
+        bail!("No PNG files found in {}", options.baseline.display());
+    }
+
+    let mut images = Vec::new();
+    for name in &baseline_files {
+        let current_path = options.current.join(name);
+        let mut entry = json!({
+            "name": name,
+            "baseline": options.baseline.join(name).to_string_lossy(),
+            "current": current_path.to_string_lossy(),
+        });
+        if !current_files.contains(name) {
+            entry["status"] = json!("missing");
+            images.push(entry);
+            continue;
+        }
+        let decoded = decode_png(&options.baseline.join(name))
+            .and_then(|before| Ok((before, decode_png(&current_path)?)));
+        let (before, after) = match decoded {
+            Ok(images) => images,
+            Err(error) => {
+                entry["status"] = json!("decode-error");
+                entry["error"] = json!(format!("{error:#}"));
+                images.push(entry);
+                continue;
+            }
+        };
+        if (before.width, before.height) != (after.width, after.height) {
+            entry["status"] = json!("size-mismatch");
+            entry["error"] = json!(format!(
+                "baseline is {}x{}, current is {}x{}",
+                before.width, before.height, after.width, after.height
+            ));
+            images.push(entry);
+            continue;
+        }
+
+        let comparison = compare_images(
+            &before,
+            &after,
+            &options.mask.regions_for(name),
+            options.pixel_threshold,
+        );
+        let diff_name = format!("diff/{name}");
+        encode_png(&options.out.join(&diff_name), &comparison.diff)?;
+        let ratio = comparison.ratio();
+        entry["status"] = json!(if ratio > options.threshold {
+            "failed"
+        } else {
+            "passed"
+        });
+        entry["width"] = json!(before.width);
+        entry["height"] = json!(before.height);
+        entry["differentPixels"] = json!(comparison.different);
+        entry["comparedPixels"] = json!(comparison.compared);
+        entry["maskedPixels"] = json!(comparison.masked);
+        entry["diffRatio"] = json!(ratio);
+        entry["perceptual"] = json!({
+            "mean": comparison.mean_delta,
+            "max": comparison.max_delta,
+        });
+        entry["diff"] = json!(diff_name);
+        images.push(entry);
+    }
+    for name in current_files
+        .iter()
+        .filter(|name| !baseline_files.contains(name))
+    {
+        images.push(json!({
+            "name": name,
+            "status": "new",
+            "current": options.current.join(name).to_string_lossy(),
+        }));
+    }
+
+    let count = |status: &str| {
+        images
+            .iter()
+            .filter(|image| image["status"] == status)
+            .count()
+    };
+    let failed =
+        count("failed") + count("missing") + count("size-mismatch") + count("decode-error");
+    let report = json!({
+        "threshold": options.threshold,
+        "pixelThreshold": options.pixel_threshold,
+        "passed": failed == 0,
+        "summary": {
+            "compared": count("passed") + count("failed"),
+            "failed": failed,
+            "new": count("new"),
+        },
+        "images": images,
+    });
+
+    fs::create_dir_all(&options.out)
+        .with_context(|| format!("Failed to create {}", options.out.display()))?;
+    let json_path = options.out.join("report.json");
+    fs::write(&json_path, serde_json::to_string_pretty(&report)?)
+        .with_context(|| format!("Failed to write {}", json_path.display()))?;
+    let html_path = options.out.join("report.html");
+    fs::write(&html_path, render_html(&report))
+        .with_context(|| format!("Failed to write {}", html_path.display()))?;
+    Ok(report)
+}
+
+/// Relative paths (with `/` separators) of the PNG files under `dir`, sorted.
+fn png_files(dir: &Path) -> Result<Vec<String>> {
+    if !dir.is_dir() {
+        bail!("Not a directory: {}", dir.display());
+    }
+    let mut files = Vec::new();
+    for entry in WalkDir::new(dir) {
+        let entry = entry.with_context(|| format!("Failed to read {}", dir.display()))?;
+        let path = entry.path();
+        let is_png = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("png"));
+        if entry.file_type().is_file() && is_png {
+            if let Ok(relative) = path.strip_prefix(dir) {
+                files.push(
+                    relative
+                        .components()
+                        .map(|part| part.as_os_str().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join("/"),
+                );
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn render_html(report: &Value) -> String {
+    let image_url = |path: &Value| path.as_str().map(file_url).unwrap_or_default();
+    let mut rows = String::new();
+    for image in report["images"].as_array().into_iter().flatten() {
+        let status = image["status"].as_str().unwrap_or("");
+        let detail = match image["diffRatio"].as_f64() {
+            Some(ratio) => format!(
+                "{:.3}% of pixels, perceptual mean {:.4} / max {:.4}",
+                ratio * 100.0,
+                image["perceptual"]["mean"].as_f64().unwrap_or(0.0),
+                image["perceptual"]["max"].as_f64().unwrap_or(0.0)
+            ),
+            None => image["error"].as_str().unwrap_or("").to_string(),
+        };
+        let cell = |url: String| {
+            if url.is_empty() {
+                "<td></td>".to_string()
+            } else {
+                format!("<td><img src=\"{}\"></td>", escape_html(&url))
+            }
+        };
+        rows.push_str(&format!(
+            "<tr class=\"{status}\"><td>{}<br><b>{status}</b><br>{}</td>{}{}{}</tr>\n",
+            escape_html(image["name"].as_str().unwrap_or("")),
+            escape_html(&detail),
+            cell(image_url(&image["baseline"])),
+            cell(image_url(&image["current"])),
+            cell(
+                image["diff"]
+                    .as_str()
+                    .map(encode_url_path)
+                    .unwrap_or_default()
+            ),
+        ));
+    }
+    format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Visual comparison</title>\n\
+<style>body{{font-family:sans-serif}}img{{max-width:320px}}td{{vertical-align:top;padding:4px}}\
+tr.failed,tr.missing,tr.size-mismatch,tr.decode-error{{background:#fdd}}tr.passed{{background:#dfd}}</style></head>\n\
+<body><h1>Visual comparison: {}</h1>\n<p>{} compared, {} failed, {} new; threshold {:.3}%</p>\n\
+<table><tr><th>Image</th><th>Baseline</th><th>Current</th><th>Diff</th></tr>\n{rows}</table></body></html>\n",
+        if report["passed"] == true { "passed" } else { "failed" },
+        report["summary"]["compared"],
+        report["summary"]["failed"],
+        report["summary"]["new"],
+        report["threshold"].as_f64().unwrap_or(0.0) * 100.0,
+    )
+}
+
+/// `file://` URL for an image outside the report directory; handles spaces,
+/// `#`, `%` and Windows drive paths.
+fn file_url(path: &str) -> String {
+    let path = Path::new(path);
+    let absolute = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut raw = absolute.to_string_lossy().to_string();
+    if cfg!(windows) {
+        raw = raw.replace('\\', "/");
+    }
+    // `canonicalize` returns `\\?\C:\...` verbatim paths on Windows.
+    let raw = raw.strip_prefix("//?/").unwrap_or(&raw);
+    let slash = if raw.starts_with('/') { "" } else { "/" };
+    format!("file://{slash}{}", encode_url_path(raw))
+}
+
+/// Percent-encodes everything but unreserved characters, `/` and `:`.
+fn encode_url_path(raw: &str) -> String {
+    let mut encoded = String::new();
+    for byte in raw.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/:".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+fn escape_html(raw: &str) -> String {
+    raw.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        compare_dirs, compare_images, decode_png, encode_png, encode_url_path, file_url,
+        parse_threshold, CompareOptions, Image, Mask,
+    };
+    use serde_json::json;
+
+    fn solid(width: u32, height: u32, rgba: [u8; 4]) -> Image {
+        Image {
+            width,
+            height,
+            rgba: rgba.repeat((width * height) as usize),
+        }
+    }
+
+    fn paint(image: &mut Image, x: u32, y: u32, rgba: [u8; 4]) {
+        let offset = ((y * image.width + x) * 4) as usize;
+        image.rgba[offset..offset + 4].copy_from_slice(&rgba);
+    }
+
+    #[test]
+    fn compare_counts_pixels_outside_mask() {
+        let mut before = solid(10, 10, [200, 200, 200, 255]);
+        paint(&mut before, 3, 3, [255, 255, 255, 255]);
+        let mut after = before.clone();
+        paint(&mut after, 1, 1, [255, 0, 0, 255]);
+        paint(&mut after, 8, 8, [0, 0, 0, 255]);
+        // Barely visible change stays below the default pixel threshold.
+        paint(&mut after, 5, 5, [201, 200, 200, 255]);
+        // Fully transparent pixels are compared as white.
+        paint(&mut after, 3, 3, [0, 0, 0, 0]);
+
+        let mask = Mask::from_value(&json!([{ "x": 7, "y": 7, "width": 3, "height": 3 }]))
+            .expect("mask should parse");
+        let result = compare_images(&before, &after, &mask.regions_for("a.png"), 0.1);
+        assert_eq!(result.masked, 9);
+        assert_eq!(result.compared, 91);
+        // The black pixel at (8, 8) is masked out.
+        assert_eq!(result.different, 1);
+        assert!(result.max_delta > 0.1 && result.max_delta <= 1.0);
+        assert_eq!(&result.diff.rgba[(11 * 4)..(11 * 4 + 4)], &[255, 0, 0, 255]);
+
+        assert_eq!(parse_threshold("0.5%").expect("threshold"), 0.005);
+        assert_eq!(parse_threshold("2").expect("threshold"), 0.02);
+        assert!(parse_threshold("abc").is_err());
+        assert!(Mask::from_value(&json!({ "regions": [{ "x": 1 }] })).is_err());
+    }
+
+    #[test]
+    fn png_round_trip_keeps_pixels() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let mut image = solid(3, 2, [10, 20, 30, 255]);
+        paint(&mut image, 2, 1, [1, 2, 3, 128]);
+        let path = dir.path().join("img.png");
+        encode_png(&path, &image).expect("encode");
+        assert_eq!(decode_png(&path).expect("decode"), image);
+    }
+
+    #[test]
+    fn compare_dirs_writes_report_and_flags_regressions() {
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let (baseline, current, out) = (
+            dir.path().join("baseline"),
+            dir.path().join("current"),
+            dir.path().join("report"),
+        );
+        let gray = solid(20, 10, [128, 128, 128, 255]);
+        let mut changed = gray.clone();
+        for x in 0..4 {
+            paint(&mut changed, x, 0, [255, 255, 0, 255]);
+        }
+        encode_png(&baseline.join("same.png"), &gray).expect("encode");
+        encode_png(&current.join("same.png"), &gray).expect("encode");
+        encode_png(&baseline.join("views/changed.png"), &gray).expect("encode");
+        encode_png(&current.join("views/changed.png"), &changed).expect("encode");
+        encode_png(&baseline.join("gone.png"), &gray).expect("encode");
+        encode_png(&current.join("extra.png"), &gray).expect("encode");
+        encode_png(&baseline.join("broken.png"), &gray).expect("encode");
+        std::fs::write(current.join("broken.png"), b"not a png").expect("write");
+
+        let mut options = CompareOptions {
+            baseline,
+            current,
+            threshold: 0.005,
+            pixel_threshold: 0.1,
+            mask: Mask::default(),
+            out: out.clone(),
+        };
+        let report = compare_dirs(&options).expect("compare should run");
+        let status = |name: &str| {
+            report["images"]
+                .as_array()
+                .expect("images")
+                .iter()
+                .find(|image| image["name"] == name)
+                .map(|image| image["status"].clone())
+        };
+        assert_eq!(report["passed"], false);
+        assert_eq!(status("same.png"), Some(json!("passed")));
+        assert_eq!(status("views/changed.png"), Some(json!("failed")));
+        assert_eq!(status("gone.png"), Some(json!("missing")));
+        assert_eq!(status("extra.png"), Some(json!("new")));
+        assert_eq!(status("broken.png"), Some(json!("decode-error")));
+        assert_eq!(report["summary"]["failed"], 3);
+        assert!(out.join("diff/views/changed.png").is_file());
+        assert!(out.join("report.json").is_file());
+        let html = std::fs::read_to_string(out.join("report.html")).expect("html");
+        assert!(html.contains("views/changed.png"));
+
+        // 4 of 200 pixels is 2%.
+        std::fs::remove_file(options.baseline.join("gone.png")).expect("remove");
+        std::fs::remove_file(options.baseline.join("broken.png")).expect("remove");
+        options.threshold = 0.02;
+        let report = compare_dirs(&options).expect("compare should run");
+        assert_eq!(report["passed"], true);
+    }
+
+    #[test]
+    fn report_urls_are_percent_encoded() {
+        assert_eq!(
+            encode_url_path("C:/shots/a b/#1 100%.png"),
+            "C:/shots/a%20b/%231%20100%25.png"
+        );
+        let dir = tempfile::tempdir().expect("tempdir should be created");
+        let path = dir.path().join("my shots#1").join("a.png");
+        encode_png(&path, &solid(1, 1, [0, 0, 0, 255])).expect("encode");
+        let url = file_url(&path.to_string_lossy());
+        assert!(url.starts_with("file:///"), "{url}");
+        assert!(url.ends_with("/my%20shots%231/a.png"), "{url}");
+    }
+}